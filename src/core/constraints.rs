@@ -1,13 +1,108 @@
 use super::types::{Parameter, ParameterConstraint};
+use crate::error::OptError;
+
+/// A feasibility constraint over the full parameter vector, in the style of
+/// a minicp-style CSP constraint: `violation` returns `0.0` when the
+/// constraint is satisfied and a positive magnitude (how far from feasible)
+/// otherwise. This is separate from [`ParameterConstraint`], which derives
+/// one parameter's value from the others; a `Constraint` only reports
+/// feasibility and is meant to be consumed by a solver's penalty or
+/// feasibility-repair strategy.
+pub trait Constraint: Send + Sync {
+    fn violation(&self, x: &[f64]) -> f64;
+
+    /// Human-readable description, for diagnostics.
+    fn describe(&self) -> String {
+        "constraint".to_string()
+    }
+}
+
+/// Linear inequality `sum(a_i * x_i) <= b`.
+pub struct LinearLessEqual {
+    pub coefficients: Vec<f64>,
+    pub bound: f64,
+}
+
+impl Constraint for LinearLessEqual {
+    fn violation(&self, x: &[f64]) -> f64 {
+        let lhs: f64 = self
+            .coefficients
+            .iter()
+            .zip(x.iter())
+            .map(|(a, xi)| a * xi)
+            .sum();
+        (lhs - self.bound).max(0.0)
+    }
+
+    fn describe(&self) -> String {
+        format!("a.x <= {}", self.bound)
+    }
+}
+
+/// Linear equality `sum(a_i * x_i) == b`.
+pub struct LinearEquals {
+    pub coefficients: Vec<f64>,
+    pub bound: f64,
+}
+
+impl Constraint for LinearEquals {
+    fn violation(&self, x: &[f64]) -> f64 {
+        let lhs: f64 = self
+            .coefficients
+            .iter()
+            .zip(x.iter())
+            .map(|(a, xi)| a * xi)
+            .sum();
+        (lhs - self.bound).abs()
+    }
+
+    fn describe(&self) -> String {
+        format!("a.x == {}", self.bound)
+    }
+}
+
+/// Ordering constraint `x[lower_idx] >= x[upper_idx]`; the violation is how
+/// far `x[upper_idx]` exceeds `x[lower_idx]`.
+pub struct OrderingConstraint {
+    pub lower_idx: usize,
+    pub upper_idx: usize,
+}
+
+impl Constraint for OrderingConstraint {
+    fn violation(&self, x: &[f64]) -> f64 {
+        (x[self.upper_idx] - x[self.lower_idx]).max(0.0)
+    }
+
+    fn describe(&self) -> String {
+        format!("x[{}] >= x[{}]", self.lower_idx, self.upper_idx)
+    }
+}
+
+/// Largest violation across a constraint set; `0.0` when feasible or empty.
+pub fn max_violation(constraints: &[Box<dyn Constraint>], x: &[f64]) -> f64 {
+    constraints
+        .iter()
+        .map(|c| c.violation(x))
+        .fold(0.0, f64::max)
+}
+
+/// Adaptive quadratic penalty: `mu * sum(violation^2)`, growing `mu` across
+/// outer iterations so early search stays free and later search is pushed
+/// toward feasibility.
+pub fn penalty(constraints: &[Box<dyn Constraint>], x: &[f64], mu: f64) -> f64 {
+    let sum_sq: f64 = constraints.iter().map(|c| c.violation(x).powi(2)).sum();
+    mu * sum_sq
+}
 
 /// Detect cyclic dependencies in parameter constraints
 ///
 /// Uses depth-first search to detect cycles in the constraint dependency graph.
-/// Returns an error if a cycle is detected, otherwise Ok(()).
+/// Returns an error naming the offending parameter if a cycle is detected,
+/// otherwise Ok(()).
 pub fn detect_cycles(
     constraints: &[ParameterConstraint],
     params: &[Parameter],
-) -> Result<(), String> {
+) -> Result<(), OptError> {
     // Build adjacency list: parameter_index -> [dependent_parameter_indices]
     let param_count = params.len();
     let mut graph: Vec<Vec<usize>> = vec![Vec::new(); param_count];
@@ -31,7 +126,7 @@ pub fn detect_cycles(
         visited: &mut [bool],
         rec_stack: &mut [bool],
         params: &[Parameter],
-    ) -> Result<(), String> {
+    ) -> Result<(), OptError> {
         visited[node] = true;
         rec_stack[node] = true;
 
@@ -39,10 +134,10 @@ pub fn detect_cycles(
             if !visited[neighbor] {
                 dfs(neighbor, graph, visited, rec_stack, params)?;
             } else if rec_stack[neighbor] {
-                return Err(format!(
-                    "Cyclic dependency detected involving parameter '{}'",
-                    params[neighbor].name
-                ));
+                return Err(OptError::Constraint {
+                    parameter: params[neighbor].name.clone(),
+                    reason: "cyclic dependency".to_string(),
+                });
             }
         }
 
@@ -62,11 +157,11 @@ pub fn detect_cycles(
 /// Validate and compile all constraints
 ///
 /// First checks for cyclic dependencies, then compiles all constraint expressions.
-/// Returns an error if validation fails or compilation fails.
+/// Returns an error naming the offending parameter if validation or compilation fails.
 pub fn validate_constraints(
     constraints: &mut [ParameterConstraint],
     params: &[Parameter],
-) -> Result<(), String> {
+) -> Result<(), OptError> {
     // First check for cycles
     detect_cycles(constraints, params)?;
 
@@ -75,7 +170,12 @@ pub fn validate_constraints(
 
     // Compile all constraints
     for constraint in constraints.iter_mut() {
-        constraint.compile(&param_names)?;
+        constraint
+            .compile(&param_names)
+            .map_err(|reason| OptError::Constraint {
+                parameter: constraint.target_param.name.clone(),
+                reason,
+            })?;
     }
 
     Ok(())
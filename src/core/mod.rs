@@ -2,6 +2,9 @@ pub mod constraints;
 pub mod expression;
 pub mod types;
 
-pub use constraints::{detect_cycles, validate_constraints};
+pub use constraints::{
+    detect_cycles, max_violation, penalty, validate_constraints, Constraint, LinearEquals,
+    LinearLessEqual, OrderingConstraint,
+};
 pub use expression::*;
 pub use types::*;
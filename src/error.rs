@@ -0,0 +1,67 @@
+//! Structured optimizer errors and their Python exception hierarchy.
+//!
+//! Everything below this boundary still returns `Result<_, String>` - that
+//! convention isn't worth disturbing everywhere it's used for internal
+//! plumbing - but the handful of call sites that cross into Python
+//! (`Optimizer::optimize`, `generate_netlist`, `validate_constraints`) wrap
+//! those strings into an [`OptError`] carrying the kind of failure and its
+//! context, so a netlist-generation failure, an NgSpice `source` failure, a
+//! constraint cycle, and a solver divergence are no longer indistinguishable
+//! `ValueError`s on the Python side.
+
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::PyErr;
+use std::fmt;
+
+create_exception!(uwasic_optimizer, OptimizerError, PyException);
+create_exception!(uwasic_optimizer, NetlistError, OptimizerError);
+create_exception!(uwasic_optimizer, SimulationError, OptimizerError);
+create_exception!(uwasic_optimizer, ConstraintError, OptimizerError);
+create_exception!(uwasic_optimizer, SolverError, OptimizerError);
+
+/// A structured optimizer failure. Each variant carries the context needed
+/// to point at the offending netlist file, SPICE command, or parameter,
+/// and converts to its matching Python exception subclass via `PyErr::from`.
+#[derive(Debug)]
+pub enum OptError {
+    /// Netlist generation or loading failed for `path`.
+    Netlist { path: String, reason: String },
+    /// An NgSpice `source`/analysis command failed.
+    Simulation { command: String, reason: String },
+    /// A constraint cycle or compilation failure involving `parameter`.
+    Constraint { parameter: String, reason: String },
+    /// A solver failed to produce a result (divergence, no feasible point, ...).
+    Solver { reason: String },
+}
+
+impl fmt::Display for OptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OptError::Netlist { path, reason } => {
+                write!(f, "netlist error ({}): {}", path, reason)
+            }
+            OptError::Simulation { command, reason } => {
+                write!(f, "simulation error running `{}`: {}", command, reason)
+            }
+            OptError::Constraint { parameter, reason } => {
+                write!(f, "constraint error for parameter '{}': {}", parameter, reason)
+            }
+            OptError::Solver { reason } => write!(f, "solver error: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for OptError {}
+
+impl From<OptError> for PyErr {
+    fn from(err: OptError) -> PyErr {
+        let message = err.to_string();
+        match err {
+            OptError::Netlist { .. } => NetlistError::new_err(message),
+            OptError::Simulation { .. } => SimulationError::new_err(message),
+            OptError::Constraint { .. } => ConstraintError::new_err(message),
+            OptError::Solver { .. } => SolverError::new_err(message),
+        }
+    }
+}
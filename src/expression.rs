@@ -12,8 +12,31 @@ enum OpCode {
     Mul,
     Div,
     Pow,
+    Neg,
+    Sin,
+    Cos,
+    Tan,
+    Exp,
+    Ln,
+    Log10,
+    Sqrt,
+    Abs,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
 }
 
+/// Tolerance for the `==`/`!=` relational opcodes.
+const RELATION_EPS: f64 = 1e-9;
+
+/// Capacity of the fixed-size evaluation stack. [`Compiler::compile`]
+/// rejects any expression whose tracked stack depth exceeds this instead of
+/// letting `evaluate` silently run off the end of the array.
+const STACK_CAPACITY: usize = 32;
+
 /// Compiled expression - data-oriented layout for cache efficiency
 #[pyclass]
 #[derive(Clone, Debug)]
@@ -21,6 +44,14 @@ pub struct CompiledExpression {
     instructions: Vec<OpCode>,
     constants: Vec<f64>, // Constant pool
     param_count: u16,
+    /// Whether the top-level expression is a relational comparison (e.g.
+    /// `"gm/id >= 10"`) rather than a plain arithmetic expression. Such
+    /// expressions evaluate to `1.0`/`0.0` and should be read with
+    /// [`Self::evaluate_bool`] instead of [`Self::evaluate`].
+    is_relational: bool,
+    /// Maximum evaluation-stack depth this expression reaches, computed
+    /// once at compile time and guaranteed `<= STACK_CAPACITY`.
+    max_depth: u16,
 }
 
 impl CompiledExpression {
@@ -30,18 +61,19 @@ impl CompiledExpression {
         if params.len() != self.param_count as usize {
             return Err("Parameter count mismatch");
         }
+        debug_assert!(self.max_depth as usize <= STACK_CAPACITY);
 
-        let mut stack = [0.0f64; 32]; // Fixed-size stack (no allocations)
+        let mut stack = [0.0f64; STACK_CAPACITY]; // Fixed-size stack (no allocations)
         let mut sp = 0usize; // Stack pointer
 
         for &inst in &self.instructions {
             match inst {
                 OpCode::LoadParam(idx) => {
-                    stack[sp] = unsafe { *params.get_unchecked(idx as usize) };
+                    stack[sp] = params[idx as usize];
                     sp += 1;
                 }
                 OpCode::LoadConst(idx) => {
-                    stack[sp] = unsafe { *self.constants.get_unchecked(idx as usize) };
+                    stack[sp] = self.constants[idx as usize];
                     sp += 1;
                 }
                 OpCode::Add => {
@@ -68,6 +100,66 @@ impl CompiledExpression {
                     sp -= 1;
                     stack[sp - 1] = stack[sp - 1].powf(stack[sp]);
                 }
+                OpCode::Neg => {
+                    stack[sp - 1] = -stack[sp - 1];
+                }
+                OpCode::Sin => {
+                    stack[sp - 1] = stack[sp - 1].sin();
+                }
+                OpCode::Cos => {
+                    stack[sp - 1] = stack[sp - 1].cos();
+                }
+                OpCode::Tan => {
+                    stack[sp - 1] = stack[sp - 1].tan();
+                }
+                OpCode::Exp => {
+                    stack[sp - 1] = stack[sp - 1].exp();
+                }
+                OpCode::Ln => {
+                    if stack[sp - 1] <= 0.0 {
+                        return Err("Logarithm of non-positive number");
+                    }
+                    stack[sp - 1] = stack[sp - 1].ln();
+                }
+                OpCode::Log10 => {
+                    if stack[sp - 1] <= 0.0 {
+                        return Err("Logarithm of non-positive number");
+                    }
+                    stack[sp - 1] = stack[sp - 1].log10();
+                }
+                OpCode::Sqrt => {
+                    if stack[sp - 1] < 0.0 {
+                        return Err("Square root of negative number");
+                    }
+                    stack[sp - 1] = stack[sp - 1].sqrt();
+                }
+                OpCode::Abs => {
+                    stack[sp - 1] = stack[sp - 1].abs();
+                }
+                OpCode::Lt => {
+                    sp -= 1;
+                    stack[sp - 1] = bool_f64(stack[sp - 1] < stack[sp]);
+                }
+                OpCode::Le => {
+                    sp -= 1;
+                    stack[sp - 1] = bool_f64(stack[sp - 1] <= stack[sp]);
+                }
+                OpCode::Gt => {
+                    sp -= 1;
+                    stack[sp - 1] = bool_f64(stack[sp - 1] > stack[sp]);
+                }
+                OpCode::Ge => {
+                    sp -= 1;
+                    stack[sp - 1] = bool_f64(stack[sp - 1] >= stack[sp]);
+                }
+                OpCode::Eq => {
+                    sp -= 1;
+                    stack[sp - 1] = bool_f64((stack[sp - 1] - stack[sp]).abs() <= RELATION_EPS);
+                }
+                OpCode::Ne => {
+                    sp -= 1;
+                    stack[sp - 1] = bool_f64((stack[sp - 1] - stack[sp]).abs() > RELATION_EPS);
+                }
             }
         }
 
@@ -78,6 +170,16 @@ impl CompiledExpression {
         Ok(stack[0])
     }
 
+    /// Evaluate a relational expression (e.g. `"gm/id >= 10"`) as a boolean,
+    /// so the optimizer's feasibility check and a reported constraint
+    /// violation can share the exact same compiled form.
+    pub fn evaluate_bool(&self, params: &[f64]) -> Result<bool, &'static str> {
+        if !self.is_relational {
+            return Err("Expression is not a relational comparison");
+        }
+        self.evaluate(params).map(|v| v != 0.0)
+    }
+
     #[inline]
     pub fn is_satisfied(
         &self,
@@ -87,6 +189,154 @@ impl CompiledExpression {
     ) -> Result<bool, &'static str> {
         self.evaluate(params).map(|v| (v - target).abs() <= tol)
     }
+
+    /// Value and exact gradient w.r.t. every parameter, computed in a
+    /// single forward-mode dual-number pass instead of `param_count + 1`
+    /// finite-difference evaluations. Each stack entry is a
+    /// `(value, gradient)` pair; `LoadParam(i)` seeds the unit gradient
+    /// `e_i`, `LoadConst` a zero gradient, and each binary op combines both
+    /// components with its own dual-number rule. Unlike [`Self::evaluate`]
+    /// this allocates (one gradient vector per stack entry), trading the
+    /// fast path's zero-allocation guarantee for an exact gradient.
+    pub fn eval_with_grad(&self, params: &[f64]) -> Result<(f64, Vec<f64>), &'static str> {
+        if params.len() != self.param_count as usize {
+            return Err("Parameter count mismatch");
+        }
+
+        let n = params.len();
+        let mut stack: Vec<(f64, Vec<f64>)> = Vec::with_capacity(self.max_depth as usize);
+
+        for &inst in &self.instructions {
+            match inst {
+                OpCode::LoadParam(idx) => {
+                    let mut grad = vec![0.0; n];
+                    grad[idx as usize] = 1.0;
+                    stack.push((params[idx as usize], grad));
+                }
+                OpCode::LoadConst(idx) => {
+                    stack.push((self.constants[idx as usize], vec![0.0; n]));
+                }
+                OpCode::Add => {
+                    let (b, gb) = stack.pop().ok_or("Stack underflow")?;
+                    let (a, mut ga) = stack.pop().ok_or("Stack underflow")?;
+                    for i in 0..n {
+                        ga[i] += gb[i];
+                    }
+                    stack.push((a + b, ga));
+                }
+                OpCode::Sub => {
+                    let (b, gb) = stack.pop().ok_or("Stack underflow")?;
+                    let (a, mut ga) = stack.pop().ok_or("Stack underflow")?;
+                    for i in 0..n {
+                        ga[i] -= gb[i];
+                    }
+                    stack.push((a - b, ga));
+                }
+                OpCode::Mul => {
+                    let (b, gb) = stack.pop().ok_or("Stack underflow")?;
+                    let (a, ga) = stack.pop().ok_or("Stack underflow")?;
+                    let grad: Vec<f64> = (0..n).map(|i| a * gb[i] + b * ga[i]).collect();
+                    stack.push((a * b, grad));
+                }
+                OpCode::Div => {
+                    let (b, gb) = stack.pop().ok_or("Stack underflow")?;
+                    let (a, ga) = stack.pop().ok_or("Stack underflow")?;
+                    if b == 0.0 {
+                        return Err("Division by zero");
+                    }
+                    let grad: Vec<f64> =
+                        (0..n).map(|i| (ga[i] * b - a * gb[i]) / (b * b)).collect();
+                    stack.push((a / b, grad));
+                }
+                OpCode::Pow => {
+                    let (b, gb) = stack.pop().ok_or("Stack underflow")?;
+                    let (a, ga) = stack.pop().ok_or("Stack underflow")?;
+                    let c = a.powf(b);
+                    let exponent_varies = gb.iter().any(|&g| g != 0.0);
+                    let grad: Vec<f64> = if exponent_varies && a > 0.0 {
+                        let ln_a = a.ln();
+                        (0..n).map(|i| c * (b / a * ga[i] + ln_a * gb[i])).collect()
+                    } else {
+                        let d = b * a.powf(b - 1.0);
+                        (0..n).map(|i| d * ga[i]).collect()
+                    };
+                    stack.push((c, grad));
+                }
+                OpCode::Neg => {
+                    let (a, ga) = stack.pop().ok_or("Stack underflow")?;
+                    let grad: Vec<f64> = ga.iter().map(|&g| -g).collect();
+                    stack.push((-a, grad));
+                }
+                OpCode::Sin => {
+                    let (a, ga) = stack.pop().ok_or("Stack underflow")?;
+                    let d = a.cos();
+                    let grad: Vec<f64> = ga.iter().map(|&g| d * g).collect();
+                    stack.push((a.sin(), grad));
+                }
+                OpCode::Cos => {
+                    let (a, ga) = stack.pop().ok_or("Stack underflow")?;
+                    let d = -a.sin();
+                    let grad: Vec<f64> = ga.iter().map(|&g| d * g).collect();
+                    stack.push((a.cos(), grad));
+                }
+                OpCode::Tan => {
+                    let (a, ga) = stack.pop().ok_or("Stack underflow")?;
+                    let c = a.cos();
+                    let d = 1.0 / (c * c);
+                    let grad: Vec<f64> = ga.iter().map(|&g| d * g).collect();
+                    stack.push((a.tan(), grad));
+                }
+                OpCode::Exp => {
+                    let (a, ga) = stack.pop().ok_or("Stack underflow")?;
+                    let c = a.exp();
+                    let grad: Vec<f64> = ga.iter().map(|&g| c * g).collect();
+                    stack.push((c, grad));
+                }
+                OpCode::Ln => {
+                    let (a, ga) = stack.pop().ok_or("Stack underflow")?;
+                    if a <= 0.0 {
+                        return Err("Logarithm of non-positive number");
+                    }
+                    let grad: Vec<f64> = ga.iter().map(|&g| g / a).collect();
+                    stack.push((a.ln(), grad));
+                }
+                OpCode::Log10 => {
+                    let (a, ga) = stack.pop().ok_or("Stack underflow")?;
+                    if a <= 0.0 {
+                        return Err("Logarithm of non-positive number");
+                    }
+                    let d = a * std::f64::consts::LN_10;
+                    let grad: Vec<f64> = ga.iter().map(|&g| g / d).collect();
+                    stack.push((a.log10(), grad));
+                }
+                OpCode::Sqrt => {
+                    let (a, ga) = stack.pop().ok_or("Stack underflow")?;
+                    if a < 0.0 {
+                        return Err("Square root of negative number");
+                    }
+                    let c = a.sqrt();
+                    let d = 2.0 * c;
+                    let grad: Vec<f64> = ga.iter().map(|&g| g / d).collect();
+                    stack.push((c, grad));
+                }
+                OpCode::Abs => {
+                    let (a, ga) = stack.pop().ok_or("Stack underflow")?;
+                    let s = a.signum();
+                    let grad: Vec<f64> = ga.iter().map(|&g| s * g).collect();
+                    stack.push((a.abs(), grad));
+                }
+                OpCode::Lt | OpCode::Le | OpCode::Gt | OpCode::Ge | OpCode::Eq | OpCode::Ne => {
+                    return Err("Gradient is undefined for relational expressions");
+                }
+            }
+        }
+
+        if stack.len() != 1 {
+            return Err("Invalid expression");
+        }
+
+        Ok(stack.into_iter().next().unwrap())
+    }
 }
 
 #[pymethods]
@@ -103,16 +353,33 @@ impl CompiledExpression {
             .map_err(|e| PyRuntimeError::new_err(format!("Expression evaluation failed: {}", e)))
     }
 
+    /// Value and exact gradient w.r.t. every parameter, as `(value, grad)`.
+    pub fn eval_grad(&self, params: Vec<f64>) -> PyResult<(f64, Vec<f64>)> {
+        self.eval_with_grad(&params)
+            .map_err(|e| PyRuntimeError::new_err(format!("Gradient evaluation failed: {}", e)))
+    }
+
     fn check(&self, params: Vec<f64>, target: f64, tolerance: f64) -> PyResult<bool> {
         self.is_satisfied(&params, target, tolerance)
             .map_err(|e| PyRuntimeError::new_err(format!("Expression check failed: {}", e)))
     }
 
+    /// Evaluate a relational expression (e.g. compiled from `"gm/id >= 10"`) as a boolean.
+    fn check_relation(&self, params: Vec<f64>) -> PyResult<bool> {
+        self.evaluate_bool(&params)
+            .map_err(|e| PyRuntimeError::new_err(format!("Relation check failed: {}", e)))
+    }
+
     #[getter]
     fn param_count(&self) -> u16 {
         self.param_count
     }
 
+    #[getter]
+    fn is_relational(&self) -> bool {
+        self.is_relational
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "CompiledExpression(params={}, instructions={}, constants={})",
@@ -127,6 +394,7 @@ struct Compiler<'a> {
     params: &'a [String],
     instructions: Vec<OpCode>,
     constants: Vec<f64>,
+    is_relational: bool,
 }
 
 impl<'a> Compiler<'a> {
@@ -135,6 +403,7 @@ impl<'a> Compiler<'a> {
             params,
             instructions: Vec::with_capacity(32),
             constants: Vec::with_capacity(8),
+            is_relational: false,
         }
     }
 
@@ -149,13 +418,23 @@ impl<'a> Compiler<'a> {
             return Err("Expression contains only whitespace".into());
         }
 
-        self.parse_expr(&cleaned)
+        self.parse_relational(&cleaned)
             .map_err(|e| format!("Parse error: {}", e))?;
 
+        let max_depth = max_stack_depth(&self.instructions);
+        if max_depth > STACK_CAPACITY {
+            return Err(format!(
+                "Expression requires a stack depth of {} which exceeds the evaluator's capacity of {}",
+                max_depth, STACK_CAPACITY
+            ));
+        }
+
         Ok(CompiledExpression {
             instructions: self.instructions,
             constants: self.constants,
+            is_relational: self.is_relational,
             param_count: self.params.len() as u16,
+            max_depth: max_depth as u16,
         })
     }
 
@@ -173,8 +452,26 @@ impl<'a> Compiler<'a> {
         self.parse_additive(s)
     }
 
+    /// Top-level relational comparison (e.g. `"gm/id >= 10"`), below
+    /// additive in precedence and non-chainable: at most one comparison
+    /// per expression, with a plain arithmetic expression on each side.
+    fn parse_relational(&mut self, s: &str) -> Result<(), String> {
+        if let Some((pos, len, opcode)) = find_relational_op(s) {
+            self.parse_additive(&s[..pos])?;
+            self.parse_additive(&s[pos + len..])?;
+            self.instructions.push(opcode);
+            self.is_relational = true;
+        } else {
+            self.parse_additive(s)?;
+        }
+        Ok(())
+    }
+
     fn parse_additive(&mut self, s: &str) -> Result<(), String> {
-        if let Some(pos) = find_op(s, &['+', '-']) {
+        // An op at position 0 has no left operand to split on - it's a
+        // leading sign (e.g. `-x`), not a binary `+`/`-`, so let it fall
+        // through to `parse_atom`, which handles unary minus.
+        if let Some(pos) = find_op(s, &['+', '-']).filter(|&pos| pos > 0) {
             self.parse_additive(&s[..pos])?;
             self.parse_multiplicative(&s[pos + 1..])?;
             self.instructions.push(if s.as_bytes()[pos] == b'+' {
@@ -219,6 +516,41 @@ impl<'a> Compiler<'a> {
             return Err("Empty sub-expression".into());
         }
 
+        // Unary minus: no left operand, so it isn't the `Sub` binary op -
+        // parse the rest as an atom and negate it.
+        if let Some(rest) = s.strip_prefix('-') {
+            self.parse_atom(rest)?;
+            self.instructions.push(OpCode::Neg);
+            return Ok(());
+        }
+
+        // Function call: `name(args)`, e.g. `sin(theta)`.
+        if let Some(open) = s.find('(') {
+            if open > 0 && s.ends_with(')') {
+                let name = &s[..open];
+                if name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                    let inner = &s[open + 1..s.len() - 1];
+                    if !is_balanced(inner) {
+                        return Err(format!("Unbalanced parentheses in '{}'", s));
+                    }
+                    let opcode = match name {
+                        "sin" => OpCode::Sin,
+                        "cos" => OpCode::Cos,
+                        "tan" => OpCode::Tan,
+                        "exp" => OpCode::Exp,
+                        "ln" => OpCode::Ln,
+                        "log10" => OpCode::Log10,
+                        "sqrt" => OpCode::Sqrt,
+                        "abs" => OpCode::Abs,
+                        _ => return Err(format!("Unknown function '{}'", name)),
+                    };
+                    self.parse_expr(inner)?;
+                    self.instructions.push(opcode);
+                    return Ok(());
+                }
+            }
+        }
+
         // Handle parentheses
         if s.starts_with('(') {
             if !s.ends_with(')') {
@@ -257,6 +589,18 @@ impl<'a> Compiler<'a> {
             return Ok(());
         }
 
+        // Pre-seeded mathematical constants
+        if s == "pi" {
+            let idx = self.add_const(std::f64::consts::PI);
+            self.instructions.push(OpCode::LoadConst(idx));
+            return Ok(());
+        }
+        if s == "e" {
+            let idx = self.add_const(std::f64::consts::E);
+            self.instructions.push(OpCode::LoadConst(idx));
+            return Ok(());
+        }
+
         // Provide helpful error message
         Err(format!(
             "Unknown identifier '{}'. Available parameters: [{}]",
@@ -282,6 +626,92 @@ fn find_op(s: &str, ops: &[char]) -> Option<usize> {
     None
 }
 
+/// Replays the emitted bytecode's effect on the stack pointer (`LoadParam`
+/// and `LoadConst` push one, unary opcodes pop one operand and push one
+/// result, binary and relational opcodes pop two and push one) to find the peak depth the evaluator's stack will reach, without
+/// actually evaluating anything.
+#[inline]
+fn max_stack_depth(instructions: &[OpCode]) -> usize {
+    let mut depth: i64 = 0;
+    let mut max_depth: i64 = 0;
+
+    for &op in instructions {
+        match op {
+            OpCode::LoadParam(_) | OpCode::LoadConst(_) => depth += 1,
+            OpCode::Neg
+            | OpCode::Sin
+            | OpCode::Cos
+            | OpCode::Tan
+            | OpCode::Exp
+            | OpCode::Ln
+            | OpCode::Log10
+            | OpCode::Sqrt
+            | OpCode::Abs => {}
+            OpCode::Add
+            | OpCode::Sub
+            | OpCode::Mul
+            | OpCode::Div
+            | OpCode::Pow
+            | OpCode::Lt
+            | OpCode::Le
+            | OpCode::Gt
+            | OpCode::Ge
+            | OpCode::Eq
+            | OpCode::Ne => depth -= 1,
+        }
+        max_depth = max_depth.max(depth);
+    }
+
+    max_depth.max(0) as usize
+}
+
+/// Scans left-to-right for a top-level (depth 0) relational operator,
+/// preferring the two-character forms (`<=`, `>=`, `==`, `!=`) over their
+/// single-character prefixes.
+#[inline]
+fn find_relational_op(s: &str) -> Option<(usize, usize, OpCode)> {
+    let bytes = s.as_bytes();
+    let mut depth = 0i32;
+
+    for i in 0..bytes.len() {
+        match bytes[i] {
+            b'(' => depth += 1,
+            b')' => depth -= 1,
+            b'<' if depth == 0 => {
+                return Some(if bytes.get(i + 1) == Some(&b'=') {
+                    (i, 2, OpCode::Le)
+                } else {
+                    (i, 1, OpCode::Lt)
+                });
+            }
+            b'>' if depth == 0 => {
+                return Some(if bytes.get(i + 1) == Some(&b'=') {
+                    (i, 2, OpCode::Ge)
+                } else {
+                    (i, 1, OpCode::Gt)
+                });
+            }
+            b'=' if depth == 0 && bytes.get(i + 1) == Some(&b'=') => {
+                return Some((i, 2, OpCode::Eq));
+            }
+            b'!' if depth == 0 && bytes.get(i + 1) == Some(&b'=') => {
+                return Some((i, 2, OpCode::Ne));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+#[inline]
+fn bool_f64(b: bool) -> f64 {
+    if b {
+        1.0
+    } else {
+        0.0
+    }
+}
+
 #[inline]
 fn is_balanced(s: &str) -> bool {
     let mut depth = 0;
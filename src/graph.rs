@@ -0,0 +1,127 @@
+//! Graphviz/DOT export of a loaded netlist, so a circuit handed to
+//! `NgSpice::load_circuit` can be visualized for debugging optimization
+//! setups instead of read as a flat list of element cards.
+
+use std::collections::BTreeSet;
+
+/// Whether device edges are rendered directed or undirected in the exported
+/// DOT graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphKind {
+    Digraph,
+    Graph,
+}
+
+impl GraphKind {
+    fn keyword(&self) -> &'static str {
+        match self {
+            GraphKind::Digraph => "digraph",
+            GraphKind::Graph => "graph",
+        }
+    }
+
+    fn edge_op(&self) -> &'static str {
+        match self {
+            GraphKind::Digraph => "->",
+            GraphKind::Graph => "--",
+        }
+    }
+}
+
+/// A single parsed netlist element card: an instance name, the nets it
+/// connects to in order, and (for simple two-terminal devices) its value.
+struct Device {
+    name: String,
+    nodes: Vec<String>,
+    value: Option<String>,
+}
+
+/// Collapse ground-equivalent net spellings (`0`, `gnd`) to a single sink
+/// vertex instead of showing one per spelling used in the netlist.
+fn normalize_net(net: &str) -> String {
+    if net == "0" || net.eq_ignore_ascii_case("gnd") {
+        "0".to_string()
+    } else {
+        net.to_string()
+    }
+}
+
+/// Parse one element card into a `Device`, or `None` for cards this exporter
+/// doesn't model (control cards, comments, unrecognized device types).
+fn parse_device(line: &str) -> Option<Device> {
+    let trimmed = line.trim();
+    let fields: Vec<&str> = trimmed.split_whitespace().collect();
+    let first = trimmed.chars().next()?;
+    let name = (*fields.first()?).to_string();
+
+    match first {
+        // Two-terminal devices: <name> <n+> <n-> <value> [...]
+        'R' | 'C' | 'L' | 'V' | 'I' => {
+            if fields.len() < 4 {
+                return None;
+            }
+            Some(Device {
+                name,
+                nodes: vec![normalize_net(fields[1]), normalize_net(fields[2])],
+                value: Some(fields[3].to_string()),
+            })
+        }
+        // Multi-terminal devices: <name> <node>... <model/subckt> [params...]
+        'M' | 'Q' | 'X' => {
+            let node_count = match first {
+                'M' => 4, // drain gate source bulk
+                'Q' => 3, // collector base emitter
+                _ => fields.len().saturating_sub(2).max(1), // X: everything but name+subckt
+            };
+            if fields.len() < node_count + 1 {
+                return None;
+            }
+            let nodes = fields[1..=node_count]
+                .iter()
+                .map(|n| normalize_net(n))
+                .collect();
+            let value = fields.get(node_count + 1).map(|s| s.to_string());
+            Some(Device { name, nodes, value })
+        }
+        _ => None,
+    }
+}
+
+/// Build a DOT-format graph of `lines`' element cards: vertices are net
+/// names (ground spellings collapsed to one sink), and edges are devices
+/// connecting consecutive node pairs, labeled with the instance name and
+/// its value/model when one is present.
+pub fn export_dot(lines: &[&str], kind: GraphKind) -> String {
+    let mut nets = BTreeSet::new();
+    let mut devices = Vec::new();
+
+    for line in lines {
+        if let Some(device) = parse_device(line) {
+            nets.extend(device.nodes.iter().cloned());
+            devices.push(device);
+        }
+    }
+
+    let mut dot = format!("{} circuit {{\n", kind.keyword());
+    for net in &nets {
+        let label = if net == "0" { "gnd" } else { net.as_str() };
+        dot.push_str(&format!("  \"{}\" [label=\"{}\"];\n", net, label));
+    }
+    for device in &devices {
+        let label = match &device.value {
+            Some(value) => format!("{} ({})", device.name, value),
+            None => device.name.clone(),
+        };
+        for pair in device.nodes.windows(2) {
+            dot.push_str(&format!(
+                "  \"{}\" {} \"{}\" [label=\"{}\"];\n",
+                pair[0],
+                kind.edge_op(),
+                pair[1],
+                label
+            ));
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
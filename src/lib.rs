@@ -1,20 +1,28 @@
 use pyo3::prelude::*;
 
 mod core;
+mod error;
+mod graph;
 mod optimization;
 mod optimizer;
 mod simulation;
+mod units;
 
 pub use core::*;
+pub use error::{ConstraintError, NetlistError, OptError, OptimizerError, SimulationError, SolverError};
+pub use graph::{export_dot, GraphKind};
 pub use optimization::*;
 pub use optimizer::Optimizer;
 pub use simulation::NgSpice;
+pub use units::{format_spice_value, parse_spice_value, SpiceValue};
 
 #[pymodule]
 fn uwasic_optimizer(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // Core types
     m.add_class::<TargetMode>()?;
     m.add_class::<RelationshipType>()?;
+    m.add_class::<TerminationReason>()?;
+    m.add_class::<Feasibility>()?;
     m.add_class::<Environment>()?;
     m.add_class::<Parameter>()?;
     m.add_class::<Target>()?;
@@ -26,7 +34,20 @@ fn uwasic_optimizer(m: &Bound<'_, PyModule>) -> PyResult<()> {
 
     // Output results
     m.add_class::<OptimizationResult>()?;
+    m.add_class::<TargetContribution>()?;
     m.add_class::<CompiledExpression>()?;
 
+    // Unit conversion helpers
+    m.add_function(wrap_pyfunction!(parse_spice_value, m)?)?;
+    m.add_function(wrap_pyfunction!(format_spice_value, m)?)?;
+
+    // Error hierarchy: `except NetlistError` / `except ConstraintError` etc.
+    // all also catch as the base `OptimizerError`.
+    m.add("OptimizerError", m.py().get_type::<OptimizerError>())?;
+    m.add("NetlistError", m.py().get_type::<NetlistError>())?;
+    m.add("SimulationError", m.py().get_type::<SimulationError>())?;
+    m.add("ConstraintError", m.py().get_type::<ConstraintError>())?;
+    m.add("SolverError", m.py().get_type::<SolverError>())?;
+
     Ok(())
 }
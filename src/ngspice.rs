@@ -4,11 +4,128 @@
 
 include!(concat!(env!("OUT_DIR"), "/ngspice_bindings.rs"));
 
+use std::collections::HashMap;
 use std::ffi::{CStr, CString};
 use std::ptr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+
+/// Mirrors the running state ngspice reports through its `BGThreadRunning`
+/// callback, so `wait` can block on a condvar instead of busy-polling
+/// `ngSpice_running()`.
+pub static BG_RUNNING: AtomicBool = AtomicBool::new(false);
+static BG_LOCK: Mutex<()> = Mutex::new(());
+static BG_CONDVAR: Condvar = Condvar::new();
+
+/// Call this from the `BGThreadRunning` callback passed to `init` so `wait`
+/// wakes up as soon as a background run finishes instead of on its next poll.
+pub fn notify_bg_state_changed(running: bool) {
+    BG_RUNNING.store(running, Ordering::SeqCst);
+    let _guard = BG_LOCK.lock().unwrap();
+    BG_CONDVAR.notify_all();
+}
+
+/// Whether a background analysis started with `run_background` is still
+/// running, as reported by `ngSpice_running()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunState {
+    Running,
+    Idle,
+}
+
+/// One point the `SendData` callback delivered mid-simulation: every
+/// vector's current value (the magnitude, for complex data), keyed by
+/// vector name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataPoint {
+    pub index: i32,
+    pub vectors: HashMap<String, f64>,
+}
+
+/// What a data sink wants to happen after observing a [`DataPoint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SinkAction {
+    Continue,
+    Halt,
+}
+
+/// A callback invoked with each [`DataPoint`] streamed from a running
+/// simulation, e.g. to report real-time progress or halt early when a
+/// monitored node crosses a threshold.
+pub type DataSink = Arc<dyn Fn(&DataPoint) -> SinkAction + Send + Sync>;
+
+/// The currently registered data sink, read by `data_sink_callback` (the
+/// `SendData` function passed to `init`). Global because ngspice's C
+/// callbacks carry no safe way back to a particular `NgSpice` instance,
+/// the same reason `BG_RUNNING`/`NGSPICE_OUTPUT` are globals.
+static DATA_SINK: Mutex<Option<DataSink>> = Mutex::new(None);
+
+/// Read one vector's value out of a raw `vecvalues`, taking the magnitude
+/// for complex data.
+unsafe fn vecvalue_as_f64(entry: &vecvalues) -> f64 {
+    if entry.is_complex {
+        (entry.creal * entry.creal + entry.cimag * entry.cimag).sqrt()
+    } else {
+        entry.creal
+    }
+}
+
+/// `SendData` callback: marshal the current vector values into a
+/// [`DataPoint`] and hand it to the registered data sink, issuing `bg_halt`
+/// if the sink returns [`SinkAction::Halt`].
+pub extern "C" fn data_sink_callback(
+    data: *mut vecvaluesall,
+    _count: i32,
+    _id: i32,
+    _user: *mut std::ffi::c_void,
+) -> i32 {
+    if data.is_null() {
+        return 0;
+    }
+
+    let point = unsafe {
+        let all = &*data;
+        let mut vectors = HashMap::with_capacity(all.veccount as usize);
+        for i in 0..all.veccount as isize {
+            let entry_ptr = *all.vecsa.offset(i);
+            if entry_ptr.is_null() {
+                continue;
+            }
+            let entry = &*entry_ptr;
+            if entry.name.is_null() {
+                continue;
+            }
+            let name = CStr::from_ptr(entry.name).to_string_lossy().into_owned();
+            vectors.insert(name, vecvalue_as_f64(entry));
+        }
+        DataPoint {
+            index: all.vecindex,
+            vectors,
+        }
+    };
+
+    let halt = DATA_SINK
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|sink| sink(&point) == SinkAction::Halt)
+        .unwrap_or(false);
+
+    if halt {
+        unsafe {
+            if let Ok(cmd) = CString::new("bg_halt") {
+                ngSpice_Command(cmd.as_ptr() as *mut i8);
+            }
+        }
+    }
+
+    0
+}
 
 pub struct NgSpice {
     user_data: *mut std::ffi::c_void,
+    loaded_lines: Mutex<Vec<String>>,
 }
 
 unsafe impl Send for NgSpice {}
@@ -18,6 +135,7 @@ impl NgSpice {
     pub fn new() -> Self {
         Self {
             user_data: ptr::null_mut(),
+            loaded_lines: Mutex::new(Vec::new()),
         }
     }
 
@@ -96,6 +214,14 @@ impl NgSpice {
         self.command(&format!("alter @{}[{}]={}", component, param, value))
     }
 
+    /// Alter a component value from a raw `f64` instead of a SPICE suffix
+    /// string, formatting it with [`crate::units::SpiceValue`] so optimizer
+    /// code working in plain `f64`s can round-trip through `alter` without
+    /// string gymnastics.
+    pub fn alter_component_value(&self, component: &str, value: f64) -> Result<(), String> {
+        self.alter_component(component, &crate::units::SpiceValue(value).to_string())
+    }
+
     pub fn load_circuit(&self, lines: &[&str]) -> Result<(), String> {
         unsafe {
             let mut c_lines: Vec<*mut i8> = Vec::with_capacity(lines.len() + 1);
@@ -118,10 +244,23 @@ impl NgSpice {
             if result != 0 {
                 return Err("NgSpice circuit loading failed".to_string());
             }
+
+            if let Ok(mut loaded) = self.loaded_lines.lock() {
+                *loaded = lines.iter().map(|l| l.to_string()).collect();
+            }
+
             Ok(())
         }
     }
 
+    /// DOT export of the circuit most recently loaded via `load_circuit`,
+    /// for piping straight into Graphviz to inspect an optimization setup.
+    pub fn current_circuit_dot(&self, kind: crate::graph::GraphKind) -> String {
+        let loaded = self.loaded_lines.lock().unwrap();
+        let lines: Vec<&str> = loaded.iter().map(|l| l.as_str()).collect();
+        crate::graph::export_dot(&lines, kind)
+    }
+
     pub fn get_vector_info(&self, vec_name: &str) -> Result<*mut vector_info, String> {
         unsafe {
             let c_name = CString::new(vec_name).map_err(|e| e.to_string())?;
@@ -190,6 +329,62 @@ impl NgSpice {
         unsafe { ngSpice_running() }
     }
 
+    /// Run `analysis` (e.g. `"tran 1n 1u"`) on ngspice's own background
+    /// thread via `bg_run` instead of blocking the caller for its duration.
+    /// Pair with `poll`/`wait` to observe completion and `halt` to cancel.
+    pub fn run_background(&self, analysis: &str) -> Result<(), String> {
+        self.commands(&[analysis, "bg_run"])
+    }
+
+    /// Non-blocking check of whether a background run is still in progress.
+    pub fn poll(&self) -> RunState {
+        if self.is_running() {
+            RunState::Running
+        } else {
+            RunState::Idle
+        }
+    }
+
+    /// Abort a background run started with `run_background`.
+    pub fn halt(&self) -> Result<(), String> {
+        self.command("bg_halt")
+    }
+
+    /// Register a sink to receive every [`DataPoint`] the `SendData`
+    /// callback streams during a run, for bounded-memory progress
+    /// monitoring or early termination instead of waiting for `get_vector`
+    /// to read back a complete waveform. Pass `data_sink_callback` as the
+    /// `data_fn` to `init` for this to take effect.
+    pub fn set_data_sink(&mut self, sink: DataSink) {
+        *DATA_SINK.lock().unwrap() = Some(sink);
+    }
+
+    /// Stop streaming to whatever sink `set_data_sink` registered.
+    pub fn clear_data_sink(&mut self) {
+        *DATA_SINK.lock().unwrap() = None;
+    }
+
+    /// Block until the background run finishes, periodically invoking
+    /// `check_signals` (e.g. `Python::check_signals`) so a Ctrl-C during a
+    /// long transient analysis is honored immediately via `halt()` instead
+    /// of only being checked between optimizer iterations. `check_signals`
+    /// returns `true` when an interrupt is pending.
+    pub fn wait(&self, check_signals: impl Fn() -> bool) -> Result<(), String> {
+        loop {
+            if self.poll() == RunState::Idle {
+                return Ok(());
+            }
+            if check_signals() {
+                self.halt()?;
+                return Err("background simulation interrupted".to_string());
+            }
+            let guard = BG_LOCK.lock().map_err(|e| e.to_string())?;
+            let _ = BG_CONDVAR
+                .wait_timeout(guard, Duration::from_millis(50))
+                .map_err(|e| e.to_string())?;
+        }
+    }
+
     pub fn set_breakpoint(&self, time: f64) -> Result<(), String> {
         unsafe {
             if !ngSpice_SetBkpt(time) {
@@ -333,6 +528,101 @@ impl NgSpice {
         }
         self.all_vecs(&plot).unwrap_or_default()
     }
+
+    /// Whether the most recent analysis actually produced a result plot, as
+    /// opposed to returning success while silently leaving no data behind
+    /// (which ngspice does for some non-convergent `op`/`dc` runs).
+    fn analysis_produced_results(&self) -> bool {
+        let plot = self.current_plot();
+        !plot.is_empty() && self.all_vecs(&plot).map(|v| !v.is_empty()).unwrap_or(false)
+    }
+
+    /// Run `analysis` (e.g. `"op"`, `"dc ..."`, `"tran ..."`), and if it
+    /// returns an error or silently produces no result plot, retry it through
+    /// an ordered ladder of standard SPICE convergence-recovery techniques
+    /// before giving up: gmin stepping, source stepping, Gear integration
+    /// with raised iteration limits, and relaxed tolerances. Each strategy's
+    /// `.options` changes are reverted before the next one is tried, so a
+    /// failed attempt never leaks into the next. Returns a structured error
+    /// listing every strategy attempted if none of them converge.
+    pub fn run_analysis_with_recovery(&self, analysis: &str) -> Result<(), String> {
+        if self.command(analysis).is_ok() && self.analysis_produced_results() {
+            return Ok(());
+        }
+
+        let mut tried = Vec::new();
+        for strategy in RecoveryStrategy::LADDER {
+            strategy.apply(self)?;
+            tried.push(strategy.label());
+            let converged = self.command(analysis).is_ok() && self.analysis_produced_results();
+            strategy.reset(self)?;
+            if converged {
+                return Ok(());
+            }
+        }
+
+        Err(format!(
+            "analysis '{}' did not converge after trying: {}",
+            analysis,
+            tried.join(", ")
+        ))
+    }
+}
+
+/// One step of the convergence-recovery ladder tried by
+/// `run_analysis_with_recovery` before giving up on a stiff analysis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RecoveryStrategy {
+    GminStepping,
+    SourceStepping,
+    GearMethod,
+    RelaxedTolerances,
+}
+
+impl RecoveryStrategy {
+    const LADDER: [RecoveryStrategy; 4] = [
+        RecoveryStrategy::GminStepping,
+        RecoveryStrategy::SourceStepping,
+        RecoveryStrategy::GearMethod,
+        RecoveryStrategy::RelaxedTolerances,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            RecoveryStrategy::GminStepping => "gminsteps=10",
+            RecoveryStrategy::SourceStepping => "sourcesteps=10",
+            RecoveryStrategy::GearMethod => "method=gear, raised itl1/itl4",
+            RecoveryStrategy::RelaxedTolerances => "relaxed reltol/abstol",
+        }
+    }
+
+    fn apply(&self, ngspice: &NgSpice) -> Result<(), String> {
+        match self {
+            RecoveryStrategy::GminStepping => ngspice.command("set gminsteps=10"),
+            RecoveryStrategy::SourceStepping => ngspice.command("set sourcesteps=10"),
+            RecoveryStrategy::GearMethod => {
+                ngspice.commands(&["set method=gear", "set itl1=500", "set itl4=500"])
+            }
+            RecoveryStrategy::RelaxedTolerances => {
+                ngspice.commands(&["set reltol=1e-2", "set abstol=1e-9"])
+            }
+        }
+    }
+
+    /// Revert this strategy's `.options` changes back to ngspice defaults so
+    /// the next rung of the ladder starts from a clean baseline.
+    fn reset(&self, ngspice: &NgSpice) -> Result<(), String> {
+        match self {
+            RecoveryStrategy::GminStepping => ngspice.command("unset gminsteps"),
+            RecoveryStrategy::SourceStepping => ngspice.command("unset sourcesteps"),
+            RecoveryStrategy::GearMethod => {
+                ngspice.commands(&["set method=trap", "set itl1=100", "set itl4=10"])
+            }
+            RecoveryStrategy::RelaxedTolerances => {
+                ngspice.commands(&["set reltol=1e-3", "set abstol=1e-12"])
+            }
+        }
+    }
 }
 
 impl Default for NgSpice {
@@ -0,0 +1,95 @@
+use super::problem::CircuitProblem;
+use super::solvers::traits::{OptimizationCallback, Problem, Solver, SolverResult};
+
+/// Values within this of a whole number are treated as integer-feasible.
+const INTEGER_TOL: f64 = 1e-6;
+/// Guards against runaway recursion on pathological bound sets.
+const MAX_DEPTH: u32 = 64;
+
+/// Solve a [`CircuitProblem`] that has integer-flagged parameters (transistor
+/// multiplicity `m`, finger count `nf`, ...) via branch-and-bound: solve the
+/// continuous relaxation, and whenever an integer-flagged parameter lands on
+/// a fractional value, branch into `[min, floor(x)]` and `[ceil(x), max]`
+/// child subproblems, recursing and keeping the best integer-feasible
+/// incumbent. Branches whose relaxed cost is already no better than the
+/// incumbent's are pruned without recursing further.
+///
+/// `solver_factory` builds a fresh solver for each relaxation solve, since a
+/// `Solver` is consumed by a single `solve()` call (e.g. `CMA-ES` resets its
+/// population each time).
+pub fn solve_mixed_integer(
+    problem: &mut CircuitProblem,
+    solver_factory: impl Fn() -> Box<dyn Solver>,
+    callback: &mut dyn OptimizationCallback,
+) -> Result<SolverResult, String> {
+    if !problem.integer_mask().iter().any(|&i| i) {
+        // No integer parameters: branch-and-bound degenerates to a single
+        // continuous solve.
+        return solver_factory().solve(problem, callback);
+    }
+
+    let mut incumbent: Option<SolverResult> = None;
+    branch(problem, &solver_factory, callback, &mut incumbent, 0)?;
+    incumbent.ok_or_else(|| "branch-and-bound found no integer-feasible solution".to_string())
+}
+
+fn branch(
+    problem: &mut CircuitProblem,
+    solver_factory: &impl Fn() -> Box<dyn Solver>,
+    callback: &mut dyn OptimizationCallback,
+    incumbent: &mut Option<SolverResult>,
+    depth: u32,
+) -> Result<(), String> {
+    if depth > MAX_DEPTH {
+        return Ok(());
+    }
+
+    let relaxed = solver_factory().solve(problem, callback)?;
+
+    // Prune: this branch's continuous relaxation is already no better than
+    // the best integer-feasible solution found so far.
+    if let Some(inc) = incumbent.as_ref() {
+        if relaxed.cost >= inc.cost {
+            return Ok(());
+        }
+    }
+
+    // Branch on the *most* fractional integer-flagged variable (largest
+    // distance from its nearest integer) rather than the first one found -
+    // this tends to resolve the least-certain dimension earliest and prune
+    // the tree faster than an arbitrary left-to-right scan.
+    let mask = problem.integer_mask();
+    let fractional = relaxed
+        .params
+        .iter()
+        .enumerate()
+        .filter(|&(i, &x)| mask[i] && (x - x.round()).abs() > INTEGER_TOL)
+        .map(|(i, &x)| (i, x, (x - x.round()).abs()))
+        .max_by(|a, b| a.2.partial_cmp(&b.2).unwrap())
+        .map(|(i, x, _)| (i, x));
+
+    let (branch_idx, x) = match fractional {
+        None => {
+            // Integer-feasible: update the incumbent.
+            if incumbent.as_ref().map_or(true, |inc| relaxed.cost < inc.cost) {
+                *incumbent = Some(relaxed);
+            }
+            return Ok(());
+        }
+        Some(pair) => pair,
+    };
+
+    let (orig_lo, orig_hi) = problem.bounds()[branch_idx];
+
+    // Branch 1: tighten the upper bound down to floor(x).
+    problem.set_upper_bound(branch_idx, x.floor())?;
+    branch(problem, solver_factory, callback, incumbent, depth + 1)?;
+    problem.set_upper_bound(branch_idx, orig_hi)?;
+
+    // Branch 2: tighten the lower bound up to ceil(x).
+    problem.set_lower_bound(branch_idx, x.ceil())?;
+    branch(problem, solver_factory, callback, incumbent, depth + 1)?;
+    problem.set_lower_bound(branch_idx, orig_lo)?;
+
+    Ok(())
+}
@@ -1,26 +1,120 @@
+use super::checkpoint::Checkpoint;
+use super::observer::{
+    json_number_array, ConsoleObserver, RunObserver, RunSummary, TargetStatus,
+};
 use super::problem::CircuitProblem;
-use super::solvers::traits::OptimizationCallback;
-use crate::core::{Target, TargetMode};
-use pyo3::Python;
+use super::problem_pool::ProblemPool;
+use super::solvers::traits::{OptimizationCallback, StopReason};
+use super::termination::{MaxIterations, StopContext, StopCriterion, TimeBudget};
+use crate::core::{Target, TargetMode, TerminationReason};
+use pyo3::types::{PyDict, PyDictMethods, PyList, PyListMethods};
+use pyo3::{Bound, PyResult, Python};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// Iteration result for tracking optimization progress
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IterationResult {
     pub params: Vec<f64>,
     pub cost: f64,
+    /// Wall-clock time this iteration took, in seconds since the previous
+    /// one (or since the run started, for the first iteration).
+    pub elapsed_secs: f64,
+    /// Every metric `extract_metrics()` returned at this iteration, keyed by
+    /// name - a superset of the configured `Target`s, captured so a run's
+    /// full convergence trace can be exported after the fact.
+    pub metrics: HashMap<String, f64>,
+}
+
+/// Output container for [`CircuitOptimizationCallback::export_history`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+/// A single target's outcome in an [`OptimizationRunResult`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TargetRecord {
+    pub metric: String,
+    pub mode: String,
+    pub target_value: f64,
+    pub achieved_value: f64,
+    pub satisfied: bool,
+}
+
+/// Structured, serializable outcome of an optimization run - the
+/// machine-readable counterpart to the console report
+/// [`CircuitOptimizationCallback::print_summary`] used to only print, so a
+/// Python caller can log, assert on, or persist a run instead of parsing
+/// stdout.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptimizationRunResult {
+    pub success: bool,
+    pub stop_reason: String,
+    pub final_cost: f64,
+    pub parameters: HashMap<String, f64>,
+    pub targets: Vec<TargetRecord>,
+    pub cost_history: Vec<f64>,
+}
+
+impl OptimizationRunResult {
+    /// Serialize to a pretty-printed JSON string.
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| format!("Failed to serialize result: {}", e))
+    }
+
+    /// Expose this result to Python as a plain `dict`, for callers that want
+    /// to work with it without a dedicated pyclass.
+    pub fn to_py_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("success", self.success)?;
+        dict.set_item("stop_reason", &self.stop_reason)?;
+        dict.set_item("final_cost", self.final_cost)?;
+        dict.set_item("parameters", &self.parameters)?;
+        dict.set_item("cost_history", &self.cost_history)?;
+
+        let targets = PyList::empty(py);
+        for target in &self.targets {
+            let entry = PyDict::new(py);
+            entry.set_item("metric", &target.metric)?;
+            entry.set_item("mode", &target.mode)?;
+            entry.set_item("target_value", target.target_value)?;
+            entry.set_item("achieved_value", target.achieved_value)?;
+            entry.set_item("satisfied", target.satisfied)?;
+            targets.append(entry)?;
+        }
+        dict.set_item("targets", targets)?;
+
+        Ok(dict)
+    }
 }
 
 /// Callback for tracking and displaying circuit optimization progress
 pub struct CircuitOptimizationCallback {
-    verbose: bool,
-    max_iterations: u32,
     iteration_count: u32,
     history: Vec<IterationResult>,
     targets: Vec<Target>,
     param_names: Vec<String>,
-    // Raw pointer to problem for accessing metrics during display
-    // This is safe because the problem outlives the callback
-    problem: *const CircuitProblem,
+    problem: Arc<CircuitProblem>,
+    // Only `Some` when `problem` was built with `with_worker_pool` - lets
+    // `evaluate_batch` fan a generation of candidates across the worker
+    // pool instead of evaluating one at a time on the live singleton.
+    pool: Option<ProblemPool>,
+    observers: Vec<Box<dyn RunObserver>>,
+    criteria: Vec<Box<dyn StopCriterion>>,
+    termination_reason: Option<TerminationReason>,
+    save_interval: u32,
+    checkpoint_path: Option<PathBuf>,
+    start_time: Instant,
+    last_iter_time: Option<Instant>,
+    max_duration: Option<Duration>,
 }
 
 impl CircuitOptimizationCallback {
@@ -29,17 +123,94 @@ impl CircuitOptimizationCallback {
         max_iterations: u32,
         targets: Vec<Target>,
         param_names: Vec<String>,
-        problem: &CircuitProblem,
+        problem: Arc<CircuitProblem>,
+        save_interval: u32,
+        checkpoint_path: Option<PathBuf>,
     ) -> Self {
+        let mut observers: Vec<Box<dyn RunObserver>> = Vec::new();
+        if verbose {
+            observers.push(Box::new(ConsoleObserver::new()));
+        }
+
+        let criteria: Vec<Box<dyn StopCriterion>> = vec![Box::new(MaxIterations(max_iterations))];
+        let pool = ProblemPool::new(problem.clone()).ok();
+
         Self {
-            verbose,
-            max_iterations,
             iteration_count: 0,
             history: Vec::new(),
             targets,
             param_names,
-            problem: problem as *const _,
+            problem,
+            pool,
+            observers,
+            criteria,
+            termination_reason: None,
+            save_interval,
+            checkpoint_path,
+            start_time: Instant::now(),
+            last_iter_time: None,
+            max_duration: None,
+        }
+    }
+
+    /// Reload a checkpoint written by a previous, interrupted run and fold
+    /// its history into `self`. Returns the last recorded parameter vector
+    /// so the caller can restart the solver from where it left off.
+    pub fn resume_from(&mut self, path: impl AsRef<std::path::Path>) -> Result<Vec<f64>, String> {
+        let checkpoint = Checkpoint::load(path)?;
+        if checkpoint.best_params.is_empty() {
+            return Err("Checkpoint has no recorded parameters to resume from".to_string());
         }
+
+        self.iteration_count = checkpoint.iteration_count;
+        let best_params = checkpoint.best_params;
+        self.history = checkpoint.history;
+        Ok(best_params)
+    }
+
+    /// Write a [`Checkpoint`] of the current history to `checkpoint_path`.
+    /// A no-op if no checkpoint path was configured.
+    fn save_checkpoint(&self) {
+        let Some(path) = &self.checkpoint_path else {
+            return;
+        };
+        let Some(best_params) = self.history.last().map(|r| r.params.clone()) else {
+            return;
+        };
+
+        let checkpoint = Checkpoint {
+            history: self.history.clone(),
+            param_names: self.param_names.clone(),
+            iteration_count: self.iteration_count,
+            best_params,
+        };
+        if let Err(e) = checkpoint.save(path) {
+            eprintln!("Warning: Failed to write checkpoint to {}: {}", path.display(), e);
+        }
+    }
+
+    /// Attach another run observer (CSV, JSONL, SQLite, ...) alongside
+    /// whatever console output `verbose` already registered.
+    pub fn with_observer(mut self, observer: Box<dyn RunObserver>) -> Self {
+        self.observers.push(observer);
+        self
+    }
+
+    /// Attach another stop criterion alongside the default `MaxIterations`.
+    pub fn with_criterion(mut self, criterion: Box<dyn StopCriterion>) -> Self {
+        self.criteria.push(criterion);
+        self
+    }
+
+    /// Bound the run by wall-clock time, in addition to `max_iterations` -
+    /// useful since each NgSpice evaluation's cost can vary wildly.
+    /// `seconds` must be finite and non-negative.
+    pub fn with_time_budget(mut self, seconds: f64) -> Result<Self, String> {
+        let duration = Duration::try_from_secs_f64(seconds)
+            .map_err(|e| format!("invalid time budget of {seconds}s: {e}"))?;
+        self.max_duration = Some(duration);
+        self.criteria.push(Box::new(TimeBudget(duration)));
+        Ok(self)
     }
 
     /// Get iteration history
@@ -47,103 +218,303 @@ impl CircuitOptimizationCallback {
         &self.history
     }
 
-    /// Print metrics comparison for current iteration
-    fn print_iteration(&self, iteration: u32, params: &[f64], cost: f64) -> Result<(), String> {
-        if !self.verbose {
-            return Ok(());
+    /// Why the run stopped, once [`OptimizationCallback::on_iteration`] has
+    /// returned [`ControlFlow::Break`].
+    pub fn termination_reason(&self) -> Option<TerminationReason> {
+        self.termination_reason
+    }
+
+    /// Narrow a [`TerminationReason`] set by a stop criterion down to the
+    /// coarser [`StopReason`] a [`Solver`](super::solvers::Solver) acts on.
+    fn stop_reason_for(reason: TerminationReason) -> StopReason {
+        match reason {
+            TerminationReason::MaxIters => StopReason::MaxIterations,
+            TerminationReason::TimeBudgetExceeded => StopReason::TimeBudget,
+            TerminationReason::UserInterrupt => {
+                StopReason::UserInterrupt("Interrupted by user (Ctrl+C)".to_string())
+            }
+            TerminationReason::TargetCostReached
+            | TerminationReason::AbsToleranceReached
+            | TerminationReason::RelToleranceReached
+            | TerminationReason::Stagnation
+            | TerminationReason::AllTargetsMet => StopReason::Converged,
         }
+    }
+
+    /// Run the simulation for `params` and return every extracted metric
+    /// alongside each target's current value and whether it is satisfied.
+    fn evaluate(&self, params: &[f64]) -> Result<(HashMap<String, f64>, Vec<TargetStatus>), String> {
+        self.problem.update_parameters(params)?;
+        self.problem.execute_measurements()?;
+        let metrics = self.problem.extract_metrics()?;
+        let targets = self.target_statuses(&metrics);
+        Ok((metrics, targets))
+    }
 
-        println!("\nIter {:4}: Cost = {:.6e}", iteration, cost);
+    /// Like [`Self::evaluate`], but for a whole batch of candidates at once
+    /// - fans them across `self.problem`'s worker pool via [`ProblemPool`]
+    /// instead of evaluating one candidate at a time on the live singleton.
+    /// Lets a population-based solver (PSO, CMA-ES, ...) report every
+    /// candidate's metrics for a generation in one call. Returns an error
+    /// if `self.problem` wasn't built with `with_worker_pool`.
+    pub fn evaluate_batch(
+        &self,
+        candidates: &[Vec<f64>],
+    ) -> Result<Vec<Result<(HashMap<String, f64>, Vec<TargetStatus>), String>>, String> {
+        let pool = self
+            .pool
+            .as_ref()
+            .ok_or("evaluate_batch requires the problem to be built with with_worker_pool()")?;
 
-        // Get metrics by running simulation
-        // Safety: problem pointer is valid for the lifetime of this callback
-        unsafe {
-            let problem = &*self.problem;
-            problem.update_parameters(params)?;
-            problem.execute_measurements()?;
-            let metrics = problem.extract_metrics()?;
+        Ok(pool
+            .evaluate_batch(candidates)
+            .into_iter()
+            .map(|result| result.map(|metrics| (metrics.clone(), self.target_statuses(&metrics))))
+            .collect())
+    }
 
-            for target in &self.targets {
-                let current = metrics.get(&target.metric).unwrap_or(&0.0);
-                let mode_str = match target.mode {
-                    TargetMode::Min => "≤",
-                    TargetMode::Max => "≥",
-                    TargetMode::Target => "=",
+    /// Each configured target's current value against `metrics`, and
+    /// whether it's satisfied - shared by [`Self::evaluate`] and
+    /// [`Self::evaluate_batch`].
+    fn target_statuses(&self, metrics: &HashMap<String, f64>) -> Vec<TargetStatus> {
+        self.targets
+            .iter()
+            .map(|target| {
+                let value = *metrics.get(&target.metric).unwrap_or(&0.0);
+                let satisfied = match target.mode {
+                    TargetMode::Min => value < target.value,
+                    TargetMode::Max => value > target.value,
+                    TargetMode::Target => {
+                        (value - target.value).abs() < (target.value.abs() * 1e-6).max(1e-9)
+                    }
                 };
-                println!(
-                    "  {:<20} Target: {:>12.6e} {} Current: {:>12.6e}",
-                    target.metric, target.value, mode_str, current
-                );
-            }
+                TargetStatus {
+                    metric: target.metric.clone(),
+                    value,
+                    target: target.value,
+                    mode: target.mode,
+                    satisfied,
+                }
+            })
+            .collect()
+    }
+
+    /// Notify every observer of the current iteration's metrics, and
+    /// evaluate every stop criterion against it.
+    fn notify_iteration(&mut self, iteration: u32, params: &[f64], cost: f64, targets: &[TargetStatus]) {
+        for observer in &mut self.observers {
+            observer.observe_iter(iteration, params, cost, targets);
+        }
+
+        if self.termination_reason.is_none() {
+            let ctx = StopContext {
+                iteration,
+                cost,
+                history: &self.history,
+                targets,
+                elapsed: self.start_time.elapsed(),
+            };
+            self.termination_reason = self.criteria.iter().find_map(|c| c.check(&ctx));
         }
+    }
 
-        Ok(())
+    /// Write the full iteration history - cost, elapsed time, parameters,
+    /// and every configured target's per-iteration metric value - to `path`
+    /// as CSV or JSON, so a run's convergence trace can be plotted or
+    /// diffed against another run offline.
+    pub fn export_history(&self, path: impl AsRef<Path>, format: ExportFormat) -> Result<(), String> {
+        match format {
+            ExportFormat::Csv => self.export_history_csv(path.as_ref()),
+            ExportFormat::Json => self.export_history_json(path.as_ref()),
+        }
     }
 
-    /// Print optimization summary
-    pub fn print_summary(&self, success: bool, stop_reason: &str) {
-        println!("\n{}", "=".repeat(80));
-        println!("OPTIMIZATION SUMMARY");
-        println!("{}", "=".repeat(80));
+    fn export_history_csv(&self, path: &Path) -> Result<(), String> {
+        let file = File::create(path)
+            .map_err(|e| format!("Failed to create history export file: {}", e))?;
+        let mut writer = BufWriter::new(file);
 
-        println!(
-            "\nStatus: {}",
-            if success { "✓ SUCCESS" } else { "✗ FAILED" }
-        );
-        println!("Stop Reason: {}", stop_reason);
-        println!("Total Iterations: {}", self.history.len());
+        let write_result = (|| -> std::io::Result<()> {
+            write!(writer, "iter,cost,elapsed_secs")?;
+            for name in &self.param_names {
+                write!(writer, ",{}", name)?;
+            }
+            for target in &self.targets {
+                write!(writer, ",{0},{0}_target,{0}_mode", target.metric)?;
+            }
+            writeln!(writer)?;
 
-        if let Some(final_result) = self.history.last() {
-            println!("\nFinal Cost: {:.6e}", final_result.cost);
-            println!("\nOptimal Parameters:");
-            for (name, &value) in self.param_names.iter().zip(final_result.params.iter()) {
-                println!("  {} = {:.6e}", name, value);
+            for (i, result) in self.history.iter().enumerate() {
+                write!(writer, "{},{:e},{:e}", i + 1, result.cost, result.elapsed_secs)?;
+                for &value in &result.params {
+                    write!(writer, ",{:e}", value)?;
+                }
+                for target in &self.targets {
+                    let value = result.metrics.get(&target.metric).copied().unwrap_or(0.0);
+                    let mode_str = match target.mode {
+                        TargetMode::Min => "min",
+                        TargetMode::Max => "max",
+                        TargetMode::Target => "target",
+                    };
+                    write!(writer, ",{:e},{:e},{}", value, target.value, mode_str)?;
+                }
+                writeln!(writer)?;
             }
-        }
+            writer.flush()
+        })();
+
+        write_result.map_err(|e| format!("Failed to write history export: {}", e))
+    }
+
+    fn export_history_json(&self, path: &Path) -> Result<(), String> {
+        let file = File::create(path)
+            .map_err(|e| format!("Failed to create history export file: {}", e))?;
+        let mut writer = BufWriter::new(file);
+
+        let rows: Vec<String> = self
+            .history
+            .iter()
+            .enumerate()
+            .map(|(i, result)| {
+                let target_entries: Vec<String> = self
+                    .targets
+                    .iter()
+                    .map(|target| {
+                        let value = result.metrics.get(&target.metric).copied().unwrap_or(0.0);
+                        let mode_str = match target.mode {
+                            TargetMode::Min => "min",
+                            TargetMode::Max => "max",
+                            TargetMode::Target => "target",
+                        };
+                        format!(
+                            "{{\"metric\":{:?},\"value\":{},\"target\":{},\"mode\":{:?}}}",
+                            target.metric, value, target.value, mode_str
+                        )
+                    })
+                    .collect();
 
-        println!("\nIteration History:");
-        println!("{:<8} {:<20}", "Iter", "Cost");
-        println!("{}", "-".repeat(30));
-        for (i, result) in self.history.iter().enumerate() {
-            println!("{:<8} {:<20.6e}", i + 1, result.cost);
+                format!(
+                    "{{\"iter\":{},\"cost\":{},\"elapsed_secs\":{},\"params\":{},\"targets\":[{}]}}",
+                    i + 1,
+                    result.cost,
+                    result.elapsed_secs,
+                    json_number_array(result.params.iter().copied()),
+                    target_entries.join(",")
+                )
+            })
+            .collect();
+
+        write!(writer, "[{}]", rows.join(","))
+            .and_then(|_| writer.flush())
+            .map_err(|e| format!("Failed to write history export: {}", e))
+    }
+
+    /// Notify every observer that the run has finished, and build the
+    /// structured, serializable [`OptimizationRunResult`] for callers that
+    /// want queryable data instead of (or in addition to) the console
+    /// report.
+    pub fn print_summary(&mut self, success: bool, reason: TerminationReason) -> OptimizationRunResult {
+        let final_result = self.history.last();
+        let stop_reason = reason.__str__().to_string();
+        let summary = RunSummary {
+            success,
+            stop_reason: stop_reason.clone(),
+            iterations: self.history.len() as u32,
+            final_cost: final_result.map(|r| r.cost).unwrap_or(0.0),
+            param_names: self.param_names.clone(),
+            final_params: final_result.map(|r| r.params.clone()).unwrap_or_default(),
+        };
+
+        for observer in &mut self.observers {
+            observer.observe_final(&summary);
         }
 
-        println!("\n{}\n", "=".repeat(80));
+        let parameters = self
+            .param_names
+            .iter()
+            .cloned()
+            .zip(summary.final_params.iter().copied())
+            .collect();
+
+        let targets = final_result
+            .map(|r| self.target_statuses(&r.metrics))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|status| TargetRecord {
+                metric: status.metric,
+                mode: match status.mode {
+                    TargetMode::Min => "min".to_string(),
+                    TargetMode::Max => "max".to_string(),
+                    TargetMode::Target => "target".to_string(),
+                },
+                target_value: status.target,
+                achieved_value: status.value,
+                satisfied: status.satisfied,
+            })
+            .collect();
+
+        OptimizationRunResult {
+            success,
+            stop_reason,
+            final_cost: summary.final_cost,
+            parameters,
+            targets,
+            cost_history: self.history.iter().map(|r| r.cost).collect(),
+        }
     }
 }
 
 impl OptimizationCallback for CircuitOptimizationCallback {
-    fn on_iteration(&mut self, iteration: u32, params: &[f64], cost: f64) -> Result<(), String> {
-        Python::with_gil(|py| {
-            if py.check_signals().is_err() {
-                return Err("Interrupted by user (Ctrl+C)".to_string());
-            }
-            Ok(())
-        })?;
+    fn on_iteration(
+        &mut self,
+        iteration: u32,
+        params: &[f64],
+        cost: f64,
+    ) -> ControlFlow<StopReason, ()> {
+        if Python::with_gil(|py| py.check_signals()).is_err() {
+            self.termination_reason = Some(TerminationReason::UserInterrupt);
+            self.save_checkpoint();
+            return ControlFlow::Break(Self::stop_reason_for(TerminationReason::UserInterrupt));
+        }
 
         self.iteration_count = iteration;
 
-        // Record iteration
+        // Record iteration, along with how long it took since the previous one
+        let now = Instant::now();
+        let elapsed_secs = self
+            .last_iter_time
+            .unwrap_or(self.start_time)
+            .elapsed()
+            .as_secs_f64();
+        self.last_iter_time = Some(now);
+
+        let (metrics, targets) = match self.evaluate(params) {
+            Ok(evaluated) => evaluated,
+            Err(e) => {
+                eprintln!("Warning: Failed to report iteration {}: {}", iteration, e);
+                self.problem.print_ngspice_output();
+                return ControlFlow::Break(StopReason::SimulationError(e));
+            }
+        };
+
         self.history.push(IterationResult {
             params: params.to_vec(),
             cost,
+            elapsed_secs,
+            metrics,
         });
 
-        // Print if verbose
-        if let Err(e) = self.print_iteration(iteration, params, cost) {
-            eprintln!("Warning: Failed to print iteration {}: {}", iteration, e);
-            // Print NgSpice output for debugging
-            unsafe {
-                let problem = &*self.problem;
-                problem.print_ngspice_output();
-            }
-            return Err(e);
-        }
+        // Notify observers (console, CSV, JSONL, SQLite, ...) and evaluate
+        // every stop criterion (including the time budget, via `elapsed`)
+        self.notify_iteration(iteration, params, cost, &targets);
 
-        Ok(())
-    }
+        if self.save_interval > 0 && iteration % self.save_interval == 0 {
+            self.save_checkpoint();
+        }
 
-    fn should_stop(&self) -> bool {
-        self.iteration_count >= self.max_iterations
+        match self.termination_reason {
+            Some(reason) => ControlFlow::Break(Self::stop_reason_for(reason)),
+            None => ControlFlow::Continue(()),
+        }
     }
 }
@@ -0,0 +1,42 @@
+//! On-disk checkpoints for long-running optimizations.
+//!
+//! A multi-hour NgSpice sweep that gets Ctrl+C'd (or crashes) loses all
+//! progress unless the run periodically snapshots itself. [`Checkpoint`]
+//! captures everything [`CircuitOptimizationCallback`](super::callback::CircuitOptimizationCallback)
+//! needs to pick back up: the full iteration history and the last recorded
+//! parameter vector to restart the solver from.
+
+use super::callback::IterationResult;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+/// Everything needed to resume an interrupted run: the history recorded so
+/// far and the parameter vector the solver should restart from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub history: Vec<IterationResult>,
+    pub param_names: Vec<String>,
+    pub iteration_count: u32,
+    pub best_params: Vec<f64>,
+}
+
+impl Checkpoint {
+    /// Write `self` to `path` as pretty-printed JSON, overwriting anything
+    /// already there.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let file = File::create(path.as_ref())
+            .map_err(|e| format!("Failed to create checkpoint file: {}", e))?;
+        serde_json::to_writer_pretty(BufWriter::new(file), self)
+            .map_err(|e| format!("Failed to write checkpoint: {}", e))
+    }
+
+    /// Load a checkpoint previously written by [`Checkpoint::save`].
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let file = File::open(path.as_ref())
+            .map_err(|e| format!("Failed to open checkpoint file: {}", e))?;
+        serde_json::from_reader(BufReader::new(file))
+            .map_err(|e| format!("Failed to parse checkpoint: {}", e))
+    }
+}
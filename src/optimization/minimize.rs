@@ -0,0 +1,143 @@
+use super::solvers::{
+    select_solver, CMAESOptimizer, ConjugateGradientOptimizer, FullNewtonOptimizer,
+    HybridOptimizer, LBFGSBOptimizer, NewtonOptimizer, OptimizationCallback, ParticleOptimizer,
+    Problem, SimulatedAnnealing, Solver, SolverResult, StopReason,
+};
+use pyo3::Python;
+use std::ops::ControlFlow;
+
+/// Lightweight [`Problem`] adapter over a bare cost closure, for callers who
+/// want a solver without standing up a full `CircuitProblem`.
+struct ClosureProblem<F: Fn(&[f64]) -> f64 + Sync> {
+    f: F,
+    initial: Vec<f64>,
+    bounds: Vec<(f64, f64)>,
+}
+
+impl<F: Fn(&[f64]) -> f64 + Sync> Problem for ClosureProblem<F> {
+    fn cost(&self, params: &[f64]) -> Result<f64, String> {
+        Ok((self.f)(params))
+    }
+
+    fn num_params(&self) -> usize {
+        self.initial.len()
+    }
+
+    fn initial_params(&self) -> &[f64] {
+        &self.initial
+    }
+
+    fn bounds(&self) -> &[(f64, f64)] {
+        &self.bounds
+    }
+
+    fn apply_constraints(&self, params: &mut [f64]) -> Result<(), String> {
+        for (p, &(lo, hi)) in params.iter_mut().zip(self.bounds.iter()) {
+            *p = p.clamp(lo, hi);
+        }
+        Ok(())
+    }
+}
+
+/// Minimal callback for ad-hoc `minimize()` calls: no history, no printing,
+/// just Ctrl+C responsiveness and a hard iteration cap.
+struct QuietCallback {
+    max_iterations: u32,
+    iteration_count: u32,
+}
+
+impl OptimizationCallback for QuietCallback {
+    fn on_iteration(
+        &mut self,
+        iteration: u32,
+        _params: &[f64],
+        _cost: f64,
+    ) -> ControlFlow<StopReason, ()> {
+        if Python::with_gil(|py| py.check_signals()).is_err() {
+            return ControlFlow::Break(StopReason::UserInterrupt(
+                "Interrupted by user (Ctrl+C)".to_string(),
+            ));
+        }
+        self.iteration_count = iteration;
+        if self.iteration_count >= self.max_iterations {
+            return ControlFlow::Break(StopReason::MaxIterations);
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// Quick-experiment facade: optimize a bare closure without implementing the
+/// full `Problem` trait, mirroring rustimization's `Funcmin` ergonomics.
+///
+/// `algo` selects the solver by name (`"cmaes"`, `"newton"`, `"particle"`,
+/// `"lbfgsb"`, `"cg"`, `"anneal"`, `"full_newton"`, `"hybrid"`); anything
+/// else (e.g. `"auto"`) routes through
+/// [`select_solver`]'s heuristic, in which case `polish` additionally
+/// requests a local-Newton refinement pass once the chosen solver converges
+/// (see [`super::PolishingSolver`]). `polish` is ignored for named solvers,
+/// which already return their own best point directly. `grad` is accepted
+/// for API parity with gradient-aware callers, but every current solver
+/// works from `cost` alone (the gradient-based ones take central finite
+/// differences of it internally), so a missing `grad` never needs a
+/// separate fallback path here -
+/// it's simply unused until a solver grows a hook for externally supplied
+/// gradients.
+pub fn minimize(
+    x0: &mut Vec<f64>,
+    f: impl Fn(&[f64]) -> f64 + Sync,
+    grad: Option<impl Fn(&[f64]) -> Vec<f64>>,
+    algo: &str,
+    bounds: Option<(Vec<f64>, Vec<f64>)>,
+    max_iterations: u32,
+    precision: f64,
+    polish: bool,
+) -> Result<SolverResult, String> {
+    let _ = grad; // reserved for analytic-gradient solvers; see doc comment above
+
+    let n = x0.len();
+    let bounds: Vec<(f64, f64)> = match bounds {
+        Some((lo, hi)) => {
+            if lo.len() != n || hi.len() != n {
+                return Err(format!(
+                    "bounds length mismatch: {} params but {} lower / {} upper bounds",
+                    n,
+                    lo.len(),
+                    hi.len()
+                ));
+            }
+            lo.into_iter().zip(hi).collect()
+        }
+        None => vec![(f64::NEG_INFINITY, f64::INFINITY); n],
+    };
+
+    let problem = ClosureProblem {
+        f,
+        initial: x0.clone(),
+        bounds,
+    };
+
+    let mut solver: Box<dyn Solver> = match algo {
+        "cmaes" => Box::new(CMAESOptimizer::new(max_iterations, precision)),
+        "newton" => Box::new(NewtonOptimizer::new(max_iterations, precision)),
+        "particle" => Box::new(ParticleOptimizer::new(max_iterations, precision)),
+        "lbfgsb" => Box::new(LBFGSBOptimizer::new(max_iterations, precision)),
+        "cg" => Box::new(ConjugateGradientOptimizer::new(max_iterations, precision)),
+        "anneal" => Box::new(SimulatedAnnealing::new(max_iterations, precision)),
+        "full_newton" => Box::new(FullNewtonOptimizer::new(max_iterations, precision)),
+        "hybrid" => Box::new(HybridOptimizer::new(max_iterations, precision)),
+        _ => {
+            let (solver, _reason) =
+                select_solver(n, problem.bounds(), false, max_iterations, precision, polish, None);
+            solver
+        }
+    };
+
+    let mut callback = QuietCallback {
+        max_iterations,
+        iteration_count: 0,
+    };
+
+    let result = solver.solve(&problem, &mut callback)?;
+    *x0 = result.params.clone();
+    Ok(result)
+}
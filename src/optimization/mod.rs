@@ -1,8 +1,39 @@
+pub mod branch_and_bound;
 pub mod callback;
+pub mod checkpoint;
+pub mod minimize;
+pub mod observer;
 pub mod problem;
+pub mod problem_pool;
+pub mod recording;
+pub mod robustness;
 pub mod solvers;
+pub mod termination;
+pub mod worker_pool;
 
-pub use callback::CircuitOptimizationCallback;
-pub use problem::CircuitProblem;
-pub use solvers::{select_solver, CMAESOptimizer, NewtonOptimizer, ParticleOptimizer};
-pub use solvers::{Problem, Solver, SolverResult};
+pub use branch_and_bound::solve_mixed_integer;
+pub use callback::{CircuitOptimizationCallback, OptimizationRunResult, TargetRecord};
+pub use checkpoint::Checkpoint;
+pub use minimize::minimize;
+pub use observer::{ConsoleObserver, CsvObserver, JsonlObserver, RunObserver, RunSummary, SqliteObserver, TargetStatus};
+pub use problem::{
+    CircuitProblem, ConstraintMode, ConstraintReport, CornerReducer, FeasibilityStrategy, Solution,
+    TargetReport,
+};
+pub use problem_pool::ProblemPool;
+pub use recording::{RecordedIteration, RecordingCallback};
+pub use robustness::{find_counterexamples, Counterexample};
+pub use termination::{
+    AbsTolerance, AllTargetsMet, MaxIterations, RelTolerance, Stagnation, StopContext,
+    StopCriterion, TargetCost, TimeBudget,
+};
+pub use worker_pool::WorkerPool;
+pub use solvers::{
+    select_solver, BaseSolverKind, CMAESOptimizer, ConjugateGradientOptimizer, ConstraintPenalty,
+    DifferentialEvolutionOptimizer, FRange, FullNewtonOptimizer, HybridOptimizer, InitMode,
+    LBFGSBOptimizer, MetaOptimizer, NewtonOptimizer, OuterSearch, ParticleOptimizer,
+    PenaltySchedule, PolishingSolver, PortfolioSolver, PsoHyperparams, SimulatedAnnealing,
+    Strategy, TempSchedule,
+};
+
+pub use solvers::{BatchHandle, OptimizationCallback, Problem, Solver, SolverResult, StopReason};
@@ -0,0 +1,263 @@
+//! Pluggable run observers, modeled on argmin's observer/slog pattern: a
+//! [`CircuitOptimizationCallback`](super::callback::CircuitOptimizationCallback)
+//! holds a `Vec<Box<dyn RunObserver>>` and notifies every one of them each
+//! iteration and once at the end of the run, instead of hardcoding
+//! `println!`. Console output is just [`ConsoleObserver`] - one more sink
+//! among CSV, newline-delimited JSON, and SQLite.
+
+use crate::core::TargetMode;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// One target's status at a given iteration, as reported to observers.
+#[derive(Debug, Clone)]
+pub struct TargetStatus {
+    pub metric: String,
+    pub value: f64,
+    pub target: f64,
+    pub mode: TargetMode,
+    pub satisfied: bool,
+}
+
+/// Summary passed to [`RunObserver::observe_final`] once a run ends.
+#[derive(Debug, Clone)]
+pub struct RunSummary {
+    pub success: bool,
+    pub stop_reason: String,
+    pub iterations: u32,
+    pub final_cost: f64,
+    pub param_names: Vec<String>,
+    pub final_params: Vec<f64>,
+}
+
+/// A sink for optimization run data: every iteration's parameters, cost, and
+/// per-target status, plus a final summary. Implement this to record a run
+/// somewhere other than stdout (CSV, JSONL, SQLite, a plotting UI, ...).
+pub trait RunObserver {
+    fn observe_iter(&mut self, iter: u32, params: &[f64], cost: f64, targets: &[TargetStatus]);
+    fn observe_final(&mut self, summary: &RunSummary);
+}
+
+/// The pre-existing `println!`-based reporting, now just one more observer.
+#[derive(Default)]
+pub struct ConsoleObserver;
+
+impl ConsoleObserver {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl RunObserver for ConsoleObserver {
+    fn observe_iter(&mut self, iter: u32, _params: &[f64], cost: f64, targets: &[TargetStatus]) {
+        println!("\nIter {:4}: Cost = {:.6e}", iter, cost);
+        for target in targets {
+            let mode_str = match target.mode {
+                TargetMode::Min => "\u{2264}",
+                TargetMode::Max => "\u{2265}",
+                TargetMode::Target => "=",
+            };
+            println!(
+                "  {:<20} Target: {:>12.6e} {} Current: {:>12.6e}",
+                target.metric, target.target, mode_str, target.value
+            );
+        }
+    }
+
+    fn observe_final(&mut self, summary: &RunSummary) {
+        println!("\n{}", "=".repeat(80));
+        println!("OPTIMIZATION SUMMARY");
+        println!("{}", "=".repeat(80));
+
+        println!(
+            "\nStatus: {}",
+            if summary.success { "\u{2713} SUCCESS" } else { "\u{2717} FAILED" }
+        );
+        println!("Stop Reason: {}", summary.stop_reason);
+        println!("Total Iterations: {}", summary.iterations);
+        println!("\nFinal Cost: {:.6e}", summary.final_cost);
+        println!("\nOptimal Parameters:");
+        for (name, &value) in summary.param_names.iter().zip(summary.final_params.iter()) {
+            println!("  {} = {:.6e}", name, value);
+        }
+        println!("\n{}\n", "=".repeat(80));
+    }
+}
+
+/// Writes one CSV row per iteration: `iter,cost,<param columns>`. The header
+/// is written on the first `observe_iter` call, once the parameter count
+/// (and therefore the column names) is known.
+pub struct CsvObserver {
+    writer: BufWriter<File>,
+    param_names: Vec<String>,
+    wrote_header: bool,
+}
+
+impl CsvObserver {
+    pub fn new(path: impl AsRef<Path>, param_names: Vec<String>) -> Result<Self, String> {
+        let file = File::create(path.as_ref())
+            .map_err(|e| format!("Failed to create CSV observer file: {}", e))?;
+        Ok(Self {
+            writer: BufWriter::new(file),
+            param_names,
+            wrote_header: false,
+        })
+    }
+
+    fn write_header(&mut self) -> std::io::Result<()> {
+        write!(self.writer, "iter,cost")?;
+        for name in &self.param_names {
+            write!(self.writer, ",{}", name)?;
+        }
+        writeln!(self.writer)
+    }
+}
+
+impl RunObserver for CsvObserver {
+    fn observe_iter(&mut self, iter: u32, params: &[f64], cost: f64, _targets: &[TargetStatus]) {
+        if !self.wrote_header {
+            let _ = self.write_header();
+            self.wrote_header = true;
+        }
+
+        let _ = write!(self.writer, "{},{:e}", iter, cost);
+        for &value in params {
+            let _ = write!(self.writer, ",{:e}", value);
+        }
+        let _ = writeln!(self.writer);
+    }
+
+    fn observe_final(&mut self, _summary: &RunSummary) {
+        let _ = self.writer.flush();
+    }
+}
+
+/// Writes one JSON object per line (newline-delimited JSON), one line per
+/// iteration plus a trailing `"final"` line.
+pub struct JsonlObserver {
+    writer: BufWriter<File>,
+}
+
+impl JsonlObserver {
+    pub fn new(path: impl AsRef<Path>) -> Result<Self, String> {
+        let file = File::create(path.as_ref())
+            .map_err(|e| format!("Failed to create JSONL observer file: {}", e))?;
+        Ok(Self {
+            writer: BufWriter::new(file),
+        })
+    }
+}
+
+/// Minimal JSON array serialization of numbers, formatted by hand for the
+/// flat line shapes these observers write.
+pub(crate) fn json_number_array(values: impl Iterator<Item = f64>) -> String {
+    let parts: Vec<String> = values.map(|v| format!("{}", v)).collect();
+    format!("[{}]", parts.join(","))
+}
+
+pub(crate) fn json_string_array<'a>(values: impl Iterator<Item = &'a str>) -> String {
+    let parts: Vec<String> = values.map(|v| format!("{:?}", v)).collect();
+    format!("[{}]", parts.join(","))
+}
+
+impl RunObserver for JsonlObserver {
+    fn observe_iter(&mut self, iter: u32, params: &[f64], cost: f64, targets: &[TargetStatus]) {
+        let target_entries: Vec<String> = targets
+            .iter()
+            .map(|t| {
+                format!(
+                    "{{\"metric\":{:?},\"value\":{},\"target\":{},\"satisfied\":{}}}",
+                    t.metric, t.value, t.target, t.satisfied
+                )
+            })
+            .collect();
+
+        let line = format!(
+            "{{\"iter\":{},\"cost\":{},\"params\":{},\"targets\":[{}]}}",
+            iter,
+            cost,
+            json_number_array(params.iter().copied()),
+            target_entries.join(",")
+        );
+        let _ = writeln!(self.writer, "{}", line);
+    }
+
+    fn observe_final(&mut self, summary: &RunSummary) {
+        let line = format!(
+            "{{\"final\":true,\"success\":{},\"stop_reason\":{:?},\"iterations\":{},\"final_cost\":{},\"param_names\":{},\"final_params\":{}}}",
+            summary.success,
+            summary.stop_reason,
+            summary.iterations,
+            summary.final_cost,
+            json_string_array(summary.param_names.iter().map(|s| s.as_str())),
+            json_number_array(summary.final_params.iter().copied())
+        );
+        let _ = writeln!(self.writer, "{}", line);
+        let _ = self.writer.flush();
+    }
+}
+
+/// Persists every iteration and the final summary into a SQLite database
+/// (`iterations(run_id, iter, cost, params_json)` and
+/// `runs(run_id, success, stop_reason, iterations, final_cost, final_params_json)`),
+/// so a run can be queried or diffed against other runs with plain SQL.
+pub struct SqliteObserver {
+    conn: rusqlite::Connection,
+    run_id: String,
+}
+
+impl SqliteObserver {
+    pub fn new(path: impl AsRef<Path>, run_id: impl Into<String>) -> Result<Self, String> {
+        let conn = rusqlite::Connection::open(path)
+            .map_err(|e| format!("Failed to open SQLite observer database: {}", e))?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS iterations (
+                run_id TEXT NOT NULL,
+                iter INTEGER NOT NULL,
+                cost REAL NOT NULL,
+                params_json TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS runs (
+                run_id TEXT PRIMARY KEY,
+                success INTEGER NOT NULL,
+                stop_reason TEXT NOT NULL,
+                iterations INTEGER NOT NULL,
+                final_cost REAL NOT NULL,
+                final_params_json TEXT NOT NULL
+            );",
+        )
+        .map_err(|e| format!("Failed to create observer tables: {}", e))?;
+
+        Ok(Self {
+            conn,
+            run_id: run_id.into(),
+        })
+    }
+}
+
+impl RunObserver for SqliteObserver {
+    fn observe_iter(&mut self, iter: u32, params: &[f64], cost: f64, _targets: &[TargetStatus]) {
+        let params_json = json_number_array(params.iter().copied());
+        let _ = self.conn.execute(
+            "INSERT INTO iterations (run_id, iter, cost, params_json) VALUES (?1, ?2, ?3, ?4)",
+            rusqlite::params![self.run_id, iter, cost, params_json],
+        );
+    }
+
+    fn observe_final(&mut self, summary: &RunSummary) {
+        let final_params_json = json_number_array(summary.final_params.iter().copied());
+        let _ = self.conn.execute(
+            "INSERT OR REPLACE INTO runs (run_id, success, stop_reason, iterations, final_cost, final_params_json)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            rusqlite::params![
+                self.run_id,
+                summary.success,
+                summary.stop_reason,
+                summary.iterations,
+                summary.final_cost,
+                final_params_json
+            ],
+        );
+    }
+}
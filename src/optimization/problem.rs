@@ -1,12 +1,17 @@
 use crate::core::*;
-use crate::optimization::solvers::traits::{OptimizationCallback, Problem};
+use crate::optimization::solvers::traits::{OptimizationCallback, Problem, StopReason};
+use crate::optimization::solvers::{
+    select_solver, CMAESOptimizer, ConjugateGradientOptimizer, ConstraintPenalty, LBFGSBOptimizer,
+    NewtonOptimizer, ParticleOptimizer, PenaltySchedule, Solver, SolverResult,
+};
+use crate::optimization::worker_pool::WorkerPool;
 use crate::optimizer::NGSPICE_OUTPUT;
 use crate::simulation::NgSpice;
 use pyo3::Python;
-use std::cell::RefCell;
 use std::collections::HashMap;
 use std::io::Write;
 use std::path::PathBuf;
+use std::sync::Mutex;
 
 const VERBOSITY_FULL: bool = false;
 
@@ -14,6 +19,18 @@ const VERBOSITY_FULL: bool = false;
 const SKY130_GRID_SIZE: f64 = 0.005e-6;
 const SKY130_GRID_INV: f64 = 1.0 / SKY130_GRID_SIZE;
 
+/// Snap a single value to its parameter's native precision: whole units for
+/// integer-flagged parameters (multiplier/finger counts), the 5nm Sky130
+/// layout grid otherwise.
+#[inline]
+fn round_to_precision(value: f64, is_integer: bool) -> f64 {
+    if is_integer {
+        value.round()
+    } else {
+        (value * SKY130_GRID_INV).round() * SKY130_GRID_SIZE
+    }
+}
+
 /// Format duration in seconds to human-readable string (e.g., "2m 30s", "1h 15m")
 fn format_duration(secs: f64) -> String {
     if secs < 60.0 {
@@ -36,6 +53,89 @@ struct ConstraintData {
     compiled: Option<crate::expression::CompiledExpression>,
 }
 
+/// How `CircuitProblem` reconciles its CSP-style [`Constraint`] set with the
+/// optimizer: either fold violations into the cost as a growing penalty, or
+/// repair candidates back toward the feasible set before they're evaluated.
+pub enum FeasibilityStrategy {
+    /// `cost += mu * sum(violation^2)`, with `mu` growing across calls.
+    AdaptivePenalty {
+        mu: std::cell::Cell<f64>,
+        growth: f64,
+        max_mu: f64,
+    },
+    /// Project candidates toward feasibility in `apply_constraints` via a
+    /// projected-subgradient repair, rather than penalizing the cost.
+    Repair,
+}
+
+impl FeasibilityStrategy {
+    pub fn adaptive_penalty(initial_mu: f64, growth: f64, max_mu: f64) -> Self {
+        Self::AdaptivePenalty {
+            mu: std::cell::Cell::new(initial_mu),
+            growth,
+            max_mu,
+        }
+    }
+}
+
+/// How `apply_constraints` reconciles a `ParameterConstraint`'s computed
+/// value with its target parameter, per `RelationshipType`.
+#[derive(Clone, Copy, Debug)]
+pub enum ConstraintMode {
+    /// Overwrite `params[target_idx]` with the computed value (clamped to
+    /// bounds) so the relationship holds exactly after every
+    /// `apply_constraints` call. The optimizer never sees a violation.
+    HardProject,
+    /// Leave `params[target_idx]` alone and instead fold the relationship's
+    /// residual into `cost` as `weight * sum(residual^2)`, flagging the
+    /// point infeasible once any residual exceeds `tolerance`. Lets the
+    /// optimizer trade a small constraint violation for a larger target
+    /// improvement instead of being hard-projected away from it.
+    SoftPenalty { weight: f64, tolerance: f64 },
+}
+
+/// How per-target errors from a [`CircuitProblem::with_corners`] sweep are
+/// combined into the single error `cost()` scores each target with.
+#[derive(Clone, Debug)]
+pub enum CornerReducer {
+    /// `max` error over corners: a design must hold spec at *every* corner,
+    /// the actual requirement for analog PVT sign-off.
+    WorstCase,
+    /// Arithmetic mean error over corners.
+    Mean,
+    /// Per-corner weights (same order as `with_corners`'s `corners`),
+    /// normalized to sum to 1. Falls back to `WorstCase` if the weights sum
+    /// to zero.
+    Weighted(Vec<f64>),
+}
+
+impl CornerReducer {
+    fn reduce(&self, errors: &[f64]) -> f64 {
+        match self {
+            CornerReducer::WorstCase => errors.iter().cloned().fold(0.0, f64::max),
+            CornerReducer::Mean => {
+                if errors.is_empty() {
+                    0.0
+                } else {
+                    errors.iter().sum::<f64>() / errors.len() as f64
+                }
+            }
+            CornerReducer::Weighted(weights) => {
+                let total_weight: f64 = weights.iter().sum();
+                if total_weight <= 0.0 {
+                    return CornerReducer::WorstCase.reduce(errors);
+                }
+                errors
+                    .iter()
+                    .zip(weights.iter())
+                    .map(|(error, weight)| error * weight)
+                    .sum::<f64>()
+                    / total_weight
+            }
+        }
+    }
+}
+
 /// Iteration result for tracking optimization progress
 #[derive(Debug, Clone)]
 pub struct IterationResult {
@@ -43,21 +143,164 @@ pub struct IterationResult {
     pub cost: f64,
 }
 
+/// One target's achieved value against its spec, from a [`Solution`].
+#[derive(Debug, Clone)]
+pub struct TargetReport {
+    pub metric: String,
+    pub mode: TargetMode,
+    pub target: f64,
+    pub achieved: f64,
+    /// Weighted error the same way [`CircuitProblem::cost`] would score it
+    /// (zero once the target's `Min`/`Max` bound is met; the raw gap for
+    /// `Target`).
+    pub error: f64,
+    pub satisfied: bool,
+}
+
+/// One `ParameterConstraint`-derived constraint's computed value against
+/// its target parameter's actual value, from a [`Solution`]. Lets a caller
+/// see *why* a design is infeasible instead of just a pass/fail flag:
+/// which relationship failed, and by how much.
+#[derive(Debug, Clone)]
+pub struct ConstraintReport {
+    /// Name of the parameter the constraint derives (`ConstraintData::target_idx`).
+    pub target_param: String,
+    pub relationship: RelationshipType,
+    /// Value the constraint's expression/relationship computes.
+    pub computed: f64,
+    /// `target_param`'s actual value at the evaluated point.
+    pub actual: f64,
+    pub satisfied: bool,
+}
+
+/// A structured record of one evaluated design point: its parameter vector,
+/// objective value, and a per-target feasibility breakdown, so callers can
+/// query the result the way a solver exposes `best_sol()`/`obj_val()`
+/// instead of scraping `IterationResult` history.
+#[derive(Debug, Clone)]
+pub struct Solution {
+    pub param_names: Vec<String>,
+    pub params: Vec<f64>,
+    pub objective: f64,
+    pub targets: Vec<TargetReport>,
+    /// Per-`ParameterConstraint` computed value and pass/fail, see
+    /// [`ConstraintReport`].
+    pub constraints: Vec<ConstraintReport>,
+    /// `Feasible` when every target is satisfied and every constraint's
+    /// computed value falls within its target parameter's actual value.
+    pub feasibility: Feasibility,
+}
+
+impl Solution {
+    pub fn obj_val(&self) -> f64 {
+        self.objective
+    }
+
+    pub fn is_feasible(&self) -> bool {
+        matches!(self.feasibility, Feasibility::Feasible)
+    }
+
+    /// Value of a named parameter, e.g. `solution.param("M1_W")`.
+    pub fn param(&self, name: &str) -> Option<f64> {
+        self.param_names
+            .iter()
+            .position(|n| n == name)
+            .map(|idx| self.params[idx])
+    }
+
+    /// Per-target report for a named metric, e.g. `solution.target("dc_gain")`.
+    pub fn target(&self, metric: &str) -> Option<&TargetReport> {
+        self.targets.iter().find(|t| t.metric == metric)
+    }
+
+    /// Per-constraint report for a named target parameter, e.g.
+    /// `solution.constraint("M1_W")`.
+    pub fn constraint(&self, target_param: &str) -> Option<&ConstraintReport> {
+        self.constraints.iter().find(|c| c.target_param == target_param)
+    }
+}
+
+impl std::fmt::Display for Solution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let targets = self.targets.iter().map(|target| {
+            format!(
+                "{}={:.3e} [{}]",
+                target.metric,
+                target.achieved,
+                if target.satisfied { "ok" } else { "MISS" }
+            )
+        });
+        let params = self
+            .param_names
+            .iter()
+            .zip(self.params.iter())
+            .map(|(name, &value)| format!("{}={:.3e}", name, value));
+
+        let entries: Vec<String> = targets.chain(params).collect();
+        write!(
+            f,
+            "Solution (obj {:.3e}, {:?}): {}",
+            self.objective, self.feasibility, entries.join(", ")
+        )
+    }
+}
+
 /// Circuit problem encapsulating simulation, parameters, and constraints
 pub struct CircuitProblem {
     params: Vec<f64>,
     bounds: Vec<(f64, f64)>,
+    /// Per-parameter integer flag (transistor `m`/`nf`-style discrete
+    /// degrees of freedom), mirrors `Parameter::integer`.
+    is_integer: Vec<bool>,
     constraints: Vec<ConstraintData>,
+    /// The `ParameterConstraint`s `constraints` above were resolved from,
+    /// kept around (rather than just their derived `ConstraintData`) so
+    /// `optimize()` can hand them to a [`crate::optimization::solvers::ConstraintPenalty`]
+    /// without re-deriving a `Vec<ParameterConstraint>` from scratch.
+    parameter_constraints: Vec<ParameterConstraint>,
     targets: Vec<Target>,
 
-    pub ngspice: RefCell<NgSpice>,
+    pub ngspice: Mutex<NgSpice>,
     tests: Vec<Test>,
 
     param_names: Vec<String>,
     temp_netlist_path: PathBuf,
+    /// Parameterized netlist (`.param` lines plus `{name}`-templated
+    /// component lines) before any candidate's values are baked in. Kept
+    /// around so `cost_batch` can render a fresh, self-contained netlist per
+    /// candidate instead of going through `alterparam` on the live
+    /// `ngspice` singleton.
+    base_netlist: Vec<String>,
     verbose: bool,
 
-    constraint_cache: RefCell<Option<(u64, Vec<f64>)>>,
+    constraint_cache: Mutex<Option<(u64, Vec<f64>)>>,
+
+    /// CSP-style feasibility constraints (distinct from the `ParameterConstraint`
+    /// expressions above, which derive a parameter's value rather than merely
+    /// report feasibility).
+    feasibility_constraints: Vec<Box<dyn Constraint>>,
+    feasibility_strategy: Option<FeasibilityStrategy>,
+
+    /// How `apply_constraints`/`cost` reconcile `ParameterConstraint`
+    /// relationships, see [`ConstraintMode`]. Defaults to `HardProject`,
+    /// matching the projection `apply_constraints` has always done.
+    constraint_mode: ConstraintMode,
+
+    /// When set, `optimize()` builds a [`ConstraintPenalty`] from
+    /// `parameter_constraints` under this schedule and attaches it to
+    /// whichever population solver [`select_solver`] picks, see
+    /// [`CircuitProblem::with_constraint_penalty_schedule`]. `None` (the
+    /// default) leaves constraint handling to `constraint_mode` alone.
+    constraint_penalty_schedule: Option<PenaltySchedule>,
+
+    /// Subprocess worker pool backing `cost_batch`, see
+    /// [`CircuitProblem::with_worker_pool`].
+    worker_pool: Option<WorkerPool>,
+
+    /// Environment corners (PVT-style sweeps) `cost()` evaluates every test
+    /// against when non-empty, see [`CircuitProblem::with_corners`].
+    corners: Vec<Vec<Environment>>,
+    corner_reducer: CornerReducer,
 }
 
 impl CircuitProblem {
@@ -72,9 +315,11 @@ impl CircuitProblem {
     ) -> Result<Self, String> {
         let params: Vec<f64> = parameters.iter().map(|p| p.value).collect();
         let bounds: Vec<(f64, f64)> = parameters.iter().map(|p| (p.min_val, p.max_val)).collect();
+        let is_integer: Vec<bool> = parameters.iter().map(|p| p.integer).collect();
         let param_names: Vec<String> = parameters.iter().map(|p| p.name.clone()).collect();
 
         // Build constraint data
+        let parameter_constraints = constraints.clone();
         let mut constraint_data = Vec::with_capacity(constraints.len());
         for constraint in constraints {
             let target_idx = parameters
@@ -177,6 +422,35 @@ impl CircuitProblem {
                     continue;
                 }
             }
+
+            // Parameterize passive/source devices (R, C, L, V, I), whose value
+            // is a positional token after the two node names rather than a
+            // keyed `ptype=` pair.
+            if trimmed
+                .chars()
+                .next()
+                .map(|c| matches!(c, 'R' | 'C' | 'L' | 'V' | 'I'))
+                .unwrap_or(false)
+            {
+                let comp_name = trimmed.split_whitespace().next().unwrap_or("");
+                if let Some(params) = component_params.get(comp_name) {
+                    if let Some((_, pname)) = params.iter().find(|(ptype, _)| ptype == "value") {
+                        let mut fields = line.split_whitespace();
+                        if let (Some(name), Some(pos), Some(neg), Some(_value)) =
+                            (fields.next(), fields.next(), fields.next(), fields.next())
+                        {
+                            let rest: Vec<&str> = fields.collect();
+                            let mut replaced = format!("{} {} {} {{{}}}", name, pos, neg, pname);
+                            for field in rest {
+                                replaced.push(' ');
+                                replaced.push_str(field);
+                            }
+                            modified_netlist.push(replaced);
+                            continue;
+                        }
+                    }
+                }
+            }
             modified_netlist.push(line.clone());
         }
 
@@ -209,17 +483,229 @@ impl CircuitProblem {
         Ok(Self {
             params,
             bounds,
+            is_integer,
             param_names,
             constraints: constraint_data,
-            ngspice: RefCell::new(ngspice),
+            parameter_constraints,
+            ngspice: Mutex::new(ngspice),
             tests: processed_tests,
             targets,
             temp_netlist_path,
+            base_netlist: modified_netlist,
             verbose,
-            constraint_cache: RefCell::new(None),
+            constraint_cache: Mutex::new(None),
+            feasibility_constraints: Vec::new(),
+            feasibility_strategy: None,
+            constraint_mode: ConstraintMode::HardProject,
+            constraint_penalty_schedule: None,
+            worker_pool: None,
+            corners: Vec::new(),
+            corner_reducer: CornerReducer::WorstCase,
         })
     }
 
+    /// Attach CSP-style feasibility constraints and pick how the optimizer
+    /// should reconcile them (adaptive penalty vs. feasibility-first repair).
+    pub fn with_feasibility_constraints(
+        mut self,
+        constraints: Vec<Box<dyn Constraint>>,
+        strategy: FeasibilityStrategy,
+    ) -> Self {
+        self.feasibility_constraints = constraints;
+        self.feasibility_strategy = Some(strategy);
+        self
+    }
+
+    /// Sweep `corners` (each a set of environment overrides, e.g. `temp` and
+    /// `vdd` pairs for a PVT corner) on every `cost()` call instead of
+    /// evaluating each test under its own environment exactly once.
+    /// Per-target errors across corners are combined with `reducer` —
+    /// typically `CornerReducer::WorstCase` so the optimized design holds
+    /// spec across the whole corner set, not just at nominal.
+    pub fn with_corners(mut self, corners: Vec<Vec<Environment>>, reducer: CornerReducer) -> Self {
+        self.corners = corners;
+        self.corner_reducer = reducer;
+        self
+    }
+
+    /// Select how `ParameterConstraint` relationships are reconciled, see
+    /// [`ConstraintMode`]. Defaults to `ConstraintMode::HardProject`.
+    pub fn with_constraint_mode(mut self, mode: ConstraintMode) -> Self {
+        self.constraint_mode = mode;
+        self
+    }
+
+    /// Have `optimize()` attach a [`ConstraintPenalty`] (built from this
+    /// problem's `ParameterConstraint`s under `schedule`) to whichever
+    /// population solver [`select_solver`] picks, so relationships
+    /// `constraint_mode` doesn't hard-project away still pull the search
+    /// toward feasibility. Independent of `constraint_mode`: the two can be
+    /// combined, e.g. a loose `SoftPenalty` tolerance plus a penalty that
+    /// only bites once a candidate drifts further than that.
+    pub fn with_constraint_penalty_schedule(mut self, schedule: PenaltySchedule) -> Self {
+        self.constraint_penalty_schedule = Some(schedule);
+        self
+    }
+
+    /// Reconstruct the `Parameter` list this problem was built from, for
+    /// APIs (like [`ConstraintPenalty::new`]) that resolve against
+    /// `ParameterConstraint`'s own `find_target_index`/`find_source_indices`
+    /// rather than the already-resolved `ConstraintData`.
+    fn parameter_list(&self) -> Vec<Parameter> {
+        self.param_names
+            .iter()
+            .zip(self.bounds.iter())
+            .zip(self.is_integer.iter())
+            .zip(self.params.iter())
+            .map(|(((name, &(min_val, max_val)), &integer), &value)| Parameter {
+                name: name.clone(),
+                value,
+                min_val,
+                max_val,
+                integer,
+            })
+            .collect()
+    }
+
+    /// Enable `cost_batch` by spawning a pool of `size` subprocess ngspice
+    /// workers. Each worker runs a fully self-contained `ngspice -b`
+    /// invocation per candidate, independent of the in-process singleton
+    /// `self.ngspice` serves the interactive `cost()` path from.
+    pub fn with_worker_pool(mut self, size: usize) -> Self {
+        self.worker_pool = Some(WorkerPool::new(size));
+        self
+    }
+
+    /// Whether `with_worker_pool` was called - [`ProblemPool::new`](super::problem_pool::ProblemPool::new)
+    /// requires this before it will wrap a problem.
+    pub(crate) fn has_worker_pool(&self) -> bool {
+        self.worker_pool.is_some()
+    }
+
+    /// Solve this problem end to end: pick a solver the way
+    /// [`minimize`](super::minimize::minimize) does (`algo` names one of
+    /// `"cmaes"`/`"newton"`/`"particle"`/`"lbfgsb"`/`"cg"`, anything else
+    /// routes through [`select_solver`]'s heuristic), then run it through
+    /// [`solve_mixed_integer`](super::branch_and_bound::solve_mixed_integer)
+    /// so any integer-flagged parameter (`nf`, `m`, unit-cell counts, ...)
+    /// comes back genuinely integral instead of needing post-hoc rounding
+    /// that can break feasibility or the optimum. A no-op wrapper when
+    /// `self` has no integer-flagged parameters - the continuous relaxation
+    /// *is* the answer. `polish` is only consulted when `algo` falls through
+    /// to [`select_solver`]'s heuristic; see [`super::PolishingSolver`].
+    pub fn optimize(
+        &mut self,
+        algo: &str,
+        max_iterations: u32,
+        precision: f64,
+        polish: bool,
+        callback: &mut dyn OptimizationCallback,
+    ) -> Result<SolverResult, String> {
+        let num_params = self.num_params();
+        let bounds = self.bounds().to_vec();
+        let has_constraints = !self.constraints.is_empty();
+        let algo = algo.to_string();
+
+        // Only built (and only consulted by the `select_solver` fallback
+        // below) when both a schedule was configured and there's something
+        // for it to penalize.
+        let constraint_penalty = if has_constraints {
+            self.constraint_penalty_schedule.map(|schedule| {
+                ConstraintPenalty::new(self.parameter_constraints.clone(), &self.parameter_list(), schedule)
+            })
+        } else {
+            None
+        };
+
+        let solver_factory = move || -> Box<dyn Solver> {
+            match algo.as_str() {
+                "cmaes" => Box::new(CMAESOptimizer::new(max_iterations, precision)),
+                "newton" => Box::new(NewtonOptimizer::new(max_iterations, precision)),
+                "particle" => Box::new(ParticleOptimizer::new(max_iterations, precision)),
+                "lbfgsb" => Box::new(LBFGSBOptimizer::new(max_iterations, precision)),
+                "cg" => Box::new(ConjugateGradientOptimizer::new(max_iterations, precision)),
+                _ => {
+                    select_solver(num_params, &bounds, has_constraints, max_iterations, precision, polish, constraint_penalty.clone()).0
+                }
+            }
+        };
+
+        super::branch_and_bound::solve_mixed_integer(self, solver_factory, callback)
+    }
+
+    /// Like `cost_batch`, but returns each candidate's full extracted metric
+    /// map instead of a derived cost scalar - what
+    /// [`ProblemPool::evaluate_batch`](super::problem_pool::ProblemPool::evaluate_batch)
+    /// calls so a caller (e.g.
+    /// [`CircuitOptimizationCallback`](super::callback::CircuitOptimizationCallback))
+    /// can fan a whole batch of candidates across the worker pool instead of
+    /// stepping through `update_parameters`/`execute_measurements`/
+    /// `extract_metrics` one candidate at a time on the single in-process
+    /// `NgSpice` singleton.
+    pub(crate) fn metrics_batch(
+        &self,
+        candidates: &[Vec<f64>],
+    ) -> Result<Vec<Result<HashMap<String, f64>, String>>, String> {
+        let pool = self
+            .worker_pool
+            .as_ref()
+            .ok_or("metrics_batch requires a worker pool; call with_worker_pool() first")?;
+
+        let netlists: Vec<Vec<String>> = candidates
+            .iter()
+            .map(|params| self.render_netlist_for(params))
+            .collect();
+
+        Ok(pool
+            .run_batch(&netlists)
+            .into_iter()
+            .map(|output| Ok(self.extract_metrics_from(&output?)))
+            .collect())
+    }
+
+    /// Project `params` toward the feasible set using a projected-subgradient
+    /// repair: for each violated constraint, step against the finite-difference
+    /// subgradient of its violation until it clears (or the iteration cap hits).
+    fn repair_toward_feasible(&self, params: &mut [f64]) {
+        const MAX_PASSES: usize = 5;
+        let h = 1e-6;
+
+        for _ in 0..MAX_PASSES {
+            let mut any_active = false;
+
+            for constraint in &self.feasibility_constraints {
+                let v = constraint.violation(params);
+                if v <= 1e-9 {
+                    continue;
+                }
+                any_active = true;
+
+                let mut grad = vec![0.0; params.len()];
+                for i in 0..params.len() {
+                    let mut bumped = params.to_vec();
+                    bumped[i] += h;
+                    grad[i] = (constraint.violation(&bumped) - v) / h;
+                }
+
+                let norm_sq: f64 = grad.iter().map(|g| g * g).sum();
+                if norm_sq > 1e-20 {
+                    let step = v / norm_sq;
+                    for (p, g) in params.iter_mut().zip(grad.iter()) {
+                        *p -= step * g;
+                    }
+                }
+            }
+
+            if !any_active {
+                break;
+            }
+        }
+
+        for (p, &(lo, hi)) in params.iter_mut().zip(self.bounds.iter()) {
+            *p = p.clamp(lo, hi);
+        }
+    }
+
     /// Get the last NgSpice output (useful for debugging)
     pub fn get_ngspice_output(&self) -> Result<Vec<String>, String> {
         let output = NGSPICE_OUTPUT
@@ -248,7 +734,10 @@ impl CircuitProblem {
             .clear();
 
         // Execute alterparam commands one by one
-        let ngspice = self.ngspice.borrow();
+        let ngspice = self
+            .ngspice
+            .lock()
+            .map_err(|e| format!("Failed to lock ngspice: {}", e))?;
         for (name, &value) in self.param_names.iter().zip(params.iter()) {
             let cmd = format!("alterparam {} = {}", name.to_lowercase(), value);
             ngspice
@@ -261,11 +750,28 @@ impl CircuitProblem {
 
     /// Execute test measurements
     pub fn execute_measurements(&self) -> Result<(), String> {
-        let ngspice = self.ngspice.borrow();
+        self.execute_measurements_for_corner(&[])
+    }
+
+    /// Same reset/run/measure sequence as `execute_measurements`, but with a
+    /// corner's environment overrides (temp/vdd/...) applied before each
+    /// test's own environment, so a test's own environment entries still win
+    /// over the corner's. `NGSPICE_OUTPUT` is cleared first so `cost`'s
+    /// corner sweep can extract each corner's metrics independently.
+    fn execute_measurements_for_corner(&self, corner: &[Environment]) -> Result<(), String> {
+        NGSPICE_OUTPUT
+            .lock()
+            .map_err(|e| format!("Failed to lock output: {}", e))?
+            .clear();
+
+        let ngspice = self
+            .ngspice
+            .lock()
+            .map_err(|e| format!("Failed to lock ngspice: {}", e))?;
 
         for test in &self.tests {
-            // Apply environment settings
-            for env in &test.environment {
+            // Apply corner environment first, then the test's own (which wins on conflict)
+            for env in corner.iter().chain(test.environment.iter()) {
                 let env_cmd = match env.name.to_lowercase().as_str() {
                     "temp" | "temperature" => format!("set temp = {}", env.value),
                     _ => format!("alterparam {} = {}", env.name.to_lowercase(), env.value),
@@ -320,7 +826,16 @@ impl CircuitProblem {
         let output = NGSPICE_OUTPUT
             .lock()
             .map_err(|e| format!("Failed to lock output: {}", e))?;
+        let metrics = self.extract_metrics_from(&output);
+        drop(output);
+        Ok(metrics)
+    }
 
+    /// Parse measurement values out of captured ngspice stdout lines,
+    /// whichever source they came from: the interactive singleton's
+    /// `NGSPICE_OUTPUT` buffer, or a subprocess worker's captured stdout in
+    /// `cost_batch`.
+    fn extract_metrics_from(&self, output: &[String]) -> HashMap<String, f64> {
         // Parse measurement values (single pass, indexed by target)
         let mut metric_values: Vec<Option<f64>> = vec![None; self.targets.len()];
 
@@ -346,7 +861,6 @@ impl CircuitProblem {
                 }
             }
         }
-        drop(output);
 
         // Build results map with penalties for missing metrics
         let mut metrics = HashMap::with_capacity(self.targets.len());
@@ -362,7 +876,293 @@ impl CircuitProblem {
             metrics.insert(target.metric.clone(), value);
         }
 
-        Ok(metrics)
+        metrics
+    }
+
+    /// Unweighted error of one achieved metric value against its target,
+    /// respecting `TargetMode::Min/Max/Target` semantics: zero once a
+    /// `Min`/`Max` bound is met, the raw gap for `Target`.
+    fn target_error(target: &Target, achieved: f64) -> f64 {
+        match target.mode {
+            TargetMode::Min if achieved >= target.value => achieved - target.value,
+            TargetMode::Max if achieved <= target.value => target.value - achieved,
+            TargetMode::Target => (achieved - target.value).abs(),
+            _ => 0.0, // Target satisfied
+        }
+    }
+
+    /// Apply the feasibility-penalty bookkeeping `cost()` uses on top of a
+    /// weighted target cost: grows `mu` each call under `AdaptivePenalty`,
+    /// passes the cost through unchanged otherwise.
+    fn apply_feasibility_penalty(&self, total_cost: f64, params: &[f64]) -> f64 {
+        match &self.feasibility_strategy {
+            Some(FeasibilityStrategy::AdaptivePenalty { mu, growth, max_mu }) => {
+                let current_mu = mu.get();
+                let penalized =
+                    total_cost + crate::core::penalty(&self.feasibility_constraints, params, current_mu);
+                mu.set((current_mu * growth).min(*max_mu));
+                penalized
+            }
+            _ => total_cost,
+        }
+    }
+
+    /// Weighted cost from already-extracted metrics, including the
+    /// feasibility-penalty bookkeeping `cost()` applies. Shared by the
+    /// interactive `cost()` path and the subprocess `cost_batch()` path so
+    /// the two agree on how a candidate is scored.
+    fn cost_from_metrics(&self, metrics: &HashMap<String, f64>, params: &[f64]) -> f64 {
+        let total_cost: f64 = self
+            .targets
+            .iter()
+            .filter_map(|target| {
+                metrics
+                    .get(&target.metric)
+                    .map(|&value| Self::target_error(target, value) * target.weight)
+            })
+            .sum();
+
+        let total_cost = total_cost + self.soft_constraint_penalty(params);
+
+        self.apply_feasibility_penalty(total_cost, params)
+    }
+
+    /// Evaluate `self.targets` once per corner in `self.corners`, reduce
+    /// each target's per-corner error with `self.corner_reducer`, and score
+    /// the weighted, reduced errors the same way `cost_from_metrics` would.
+    /// This is the worst-case (or mean/weighted) robustness path `cost()`
+    /// takes when corners are configured via `with_corners`.
+    fn cost_across_corners(&self, params: &[f64]) -> Result<f64, String> {
+        let mut per_target_errors: Vec<Vec<f64>> =
+            vec![Vec::with_capacity(self.corners.len()); self.targets.len()];
+
+        for corner in &self.corners {
+            self.execute_measurements_for_corner(corner)?;
+            let metrics = self.extract_metrics()?;
+
+            for (errors, target) in per_target_errors.iter_mut().zip(self.targets.iter()) {
+                let achieved = *metrics.get(&target.metric).unwrap_or(&0.0);
+                errors.push(Self::target_error(target, achieved));
+            }
+        }
+
+        let total_cost: f64 = self
+            .targets
+            .iter()
+            .zip(per_target_errors.iter())
+            .map(|(target, errors)| self.corner_reducer.reduce(errors) * target.weight)
+            .sum();
+
+        Ok(self.apply_feasibility_penalty(total_cost, params))
+    }
+
+    /// Parallel counterpart to `cost_across_corners`: since
+    /// `merge_tests_by_environment` already proves each `(environment,
+    /// analysis_type)` group is independent, render one self-contained
+    /// netlist per corner (the same `.param`-baked rendering `cost_batch`
+    /// uses per-candidate) and fan them across `self.worker_pool`'s
+    /// subprocess ngspice instances instead of replaying corners serially
+    /// through the single in-process singleton. Each subprocess keeps its
+    /// own captured stdout, so corners never contend over the global
+    /// `NGSPICE_OUTPUT` the interactive path uses.
+    ///
+    /// Falls back to `cost()` when there are no corners to parallelize, and
+    /// to the serial `cost_across_corners` when no worker pool was attached
+    /// (`with_worker_pool` not called) - so `cost_parallel` with a
+    /// single-worker pool reproduces single-threaded behavior exactly.
+    pub fn cost_parallel(&self, params: &[f64]) -> Result<f64, String> {
+        if self.corners.is_empty() {
+            return self.cost(params);
+        }
+
+        let Some(pool) = &self.worker_pool else {
+            self.update_parameters(params)?;
+            return self.cost_across_corners(params);
+        };
+
+        let netlists: Vec<Vec<String>> = self
+            .corners
+            .iter()
+            .map(|corner| self.render_netlist_for_corner(params, corner))
+            .collect();
+
+        let mut per_target_errors: Vec<Vec<f64>> =
+            vec![Vec::with_capacity(self.corners.len()); self.targets.len()];
+
+        for output in pool.run_batch(&netlists) {
+            let metrics = self.extract_metrics_from(&output?);
+            for (errors, target) in per_target_errors.iter_mut().zip(self.targets.iter()) {
+                let achieved = *metrics.get(&target.metric).unwrap_or(&0.0);
+                errors.push(Self::target_error(target, achieved));
+            }
+        }
+
+        let total_cost: f64 = self
+            .targets
+            .iter()
+            .zip(per_target_errors.iter())
+            .map(|(target, errors)| self.corner_reducer.reduce(errors) * target.weight)
+            .sum();
+
+        Ok(self.apply_feasibility_penalty(total_cost, params))
+    }
+
+    /// Run one simulation at `params` and assemble a structured [`Solution`]
+    /// report: the objective value, a per-target achieved/error/satisfied
+    /// breakdown, a per-constraint computed-value breakdown, and an overall
+    /// [`Feasibility`] flag, so a caller (or a CLI summary) can explain why
+    /// a design point scored what it did rather than just printing a
+    /// number.
+    pub fn evaluate_full(&self, params: &[f64]) -> Result<Solution, String> {
+        self.update_parameters(params)?;
+        self.execute_measurements()?;
+        let metrics = self.extract_metrics()?;
+        let objective = self.cost_from_metrics(&metrics, params);
+
+        let mut targets = Vec::with_capacity(self.targets.len());
+        let mut all_targets_satisfied = true;
+        for target in &self.targets {
+            let achieved = *metrics.get(&target.metric).unwrap_or(&0.0);
+            let error = Self::target_error(target, achieved);
+            let satisfied = match target.mode {
+                TargetMode::Target => error <= (target.value.abs() * 1e-6).max(1e-9),
+                _ => error <= 0.0,
+            };
+            all_targets_satisfied &= satisfied;
+
+            targets.push(TargetReport {
+                metric: target.metric.clone(),
+                mode: target.mode,
+                target: target.value,
+                achieved,
+                error: error * target.weight,
+                satisfied,
+            });
+        }
+
+        let constraints = self.constraint_reports(params)?;
+        let all_constraints_satisfied = constraints.iter().all(|c| c.satisfied);
+
+        Ok(Solution {
+            param_names: self.param_names.clone(),
+            params: params.to_vec(),
+            objective,
+            feasibility: (all_targets_satisfied && all_constraints_satisfied).into(),
+            targets,
+            constraints,
+        })
+    }
+
+    /// Evaluate every `ParameterConstraint`-derived [`ConstraintData`] at
+    /// `params`, pairing its computed value against the target parameter's
+    /// actual value and recording whether the relationship holds, within a
+    /// small numerical tolerance.
+    fn constraint_reports(&self, params: &[f64]) -> Result<Vec<ConstraintReport>, String> {
+        const EPS: f64 = 1e-6;
+        let tolerance = match self.constraint_mode {
+            ConstraintMode::HardProject => EPS,
+            ConstraintMode::SoftPenalty { tolerance, .. } => tolerance,
+        };
+
+        let computed = self.cached_constraint_values(params)?;
+
+        Ok(self
+            .constraints
+            .iter()
+            .zip(computed.iter())
+            .map(|(constraint, &computed)| {
+                let actual = params[constraint.target_idx];
+                let residual = Self::constraint_residual(constraint.relationship, actual, computed);
+                ConstraintReport {
+                    target_param: self.param_names[constraint.target_idx].clone(),
+                    relationship: constraint.relationship,
+                    computed,
+                    actual,
+                    satisfied: residual <= tolerance,
+                }
+            })
+            .collect())
+    }
+
+    /// Render a fully self-contained netlist for `params`: `base_netlist`
+    /// with each `.param` line's value swapped for the candidate's, plus a
+    /// `.control` block that replays the same reset/run/measure sequence
+    /// `execute_measurements` drives interactively. This is what each
+    /// `cost_batch` worker actually runs, since a subprocess has no
+    /// `alterparam`/`reset`/`run` session to talk to.
+    fn render_netlist_for(&self, params: &[f64]) -> Vec<String> {
+        self.render_netlist_for_corner(params, &[])
+    }
+
+    /// Same as `render_netlist_for`, but with `corner`'s environment
+    /// overrides (temp/vdd/...) applied ahead of each test's own - the
+    /// per-corner counterpart `cost_parallel` renders so a corner sweep can
+    /// fan out across `self.worker_pool` instead of replaying serially
+    /// through `execute_measurements_for_corner`.
+    fn render_netlist_for_corner(&self, params: &[f64], corner: &[Environment]) -> Vec<String> {
+        let mut netlist = self.base_netlist.clone();
+
+        for (name, &value) in self.param_names.iter().zip(params.iter()) {
+            let prefix = format!(".param {} = ", name);
+            if let Some(line) = netlist.iter_mut().find(|l| l.starts_with(&prefix)) {
+                *line = format!("{}{}", prefix, value);
+            }
+        }
+
+        let control_block = self.render_control_block(corner);
+        let end_pos = netlist
+            .iter()
+            .position(|l| l.trim() == ".end")
+            .unwrap_or(netlist.len());
+        netlist.splice(end_pos..end_pos, control_block);
+
+        netlist
+    }
+
+    /// Build the `.control ... .endc` block a subprocess worker needs to
+    /// reproduce `execute_measurements`' reset/run/measure sequence for
+    /// every (already-merged) test, with `corner`'s environment overrides
+    /// applied first (a test's own environment still wins on conflict, same
+    /// precedence as `execute_measurements_for_corner`).
+    fn render_control_block(&self, corner: &[Environment]) -> Vec<String> {
+        let mut block = vec![".control".to_string()];
+
+        for test in &self.tests {
+            for env in corner.iter().chain(test.environment.iter()) {
+                let env_cmd = match env.name.to_lowercase().as_str() {
+                    "temp" | "temperature" => format!("set temp = {}", env.value),
+                    _ => format!("alterparam {} = {}", env.name.to_lowercase(), env.value),
+                };
+                block.push(env_cmd);
+            }
+
+            block.push("reset".to_string());
+
+            let analysis_line = test.spice_code.lines().find(|line| {
+                let t = line.trim();
+                t.starts_with(".ac ")
+                    || t.starts_with(".dc ")
+                    || t.starts_with(".tran ")
+                    || t.starts_with(".op")
+            });
+            if let Some(analysis_line) = analysis_line {
+                block.push(analysis_line.trim()[1..].to_string());
+            }
+
+            for line in test.spice_code.lines() {
+                let trimmed = line.trim();
+                if !trimmed.is_empty()
+                    && !trimmed.starts_with('*')
+                    && !trimmed.starts_with('.')
+                    && trimmed != "run"
+                {
+                    block.push(trimmed.to_string());
+                }
+            }
+        }
+
+        block.push(".endc".to_string());
+        block
     }
 
     /// Get targets (for callback access)
@@ -370,11 +1170,121 @@ impl CircuitProblem {
         &self.targets
     }
 
+    /// Per-parameter integer flags, for branch-and-bound over discrete
+    /// degrees of freedom (see [`crate::optimization::branch_and_bound`]).
+    pub fn integer_mask(&self) -> &[bool] {
+        &self.is_integer
+    }
+
+    /// Override the lower bound of a single parameter, e.g. to tighten the
+    /// feasible box for a branch-and-bound child problem.
+    pub fn set_lower_bound(&mut self, index: usize, value: f64) -> Result<(), String> {
+        let (_, max) = self
+            .bounds
+            .get(index)
+            .copied()
+            .ok_or_else(|| format!("Parameter index {} out of range", index))?;
+        self.bounds[index] = (value, max);
+        Ok(())
+    }
+
+    /// Override the upper bound of a single parameter.
+    pub fn set_upper_bound(&mut self, index: usize, value: f64) -> Result<(), String> {
+        let (min, _) = self
+            .bounds
+            .get(index)
+            .copied()
+            .ok_or_else(|| format!("Parameter index {} out of range", index))?;
+        self.bounds[index] = (min, value);
+        Ok(())
+    }
+
     /// Get parameter names (for callback access)
     pub fn param_names(&self) -> &[String] {
         &self.param_names
     }
 
+    /// `evaluate_all_constraints`, reusing `constraint_cache` when the hash
+    /// of just the *source* parameters (the ones any constraint actually
+    /// reads) hasn't changed since the last call. Shared by the
+    /// hard-projection path in `apply_constraints` and the soft-penalty
+    /// residual path in `cost`, so a constraint's computed value is only
+    /// recomputed once per distinct parameter vector regardless of which
+    /// path asks for it first.
+    fn cached_constraint_values(&self, params: &[f64]) -> Result<Vec<f64>, String> {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        for (i, &param) in params.iter().enumerate() {
+            if self
+                .constraints
+                .iter()
+                .any(|c| c.source_indices.contains(&i))
+            {
+                std::hash::Hash::hash(&param.to_bits(), &mut hasher);
+            }
+        }
+        let param_hash = std::hash::Hasher::finish(&hasher);
+
+        let mut cache = self
+            .constraint_cache
+            .lock()
+            .map_err(|e| format!("Failed to lock constraint cache: {}", e))?;
+        if let Some((cached_hash, cached_results)) = cache.as_ref() {
+            if *cached_hash == param_hash {
+                return Ok(cached_results.clone());
+            }
+        }
+
+        let results = self.evaluate_all_constraints(params)?;
+        *cache = Some((param_hash, results.clone()));
+        Ok(results)
+    }
+
+    /// Per-constraint residual at `params`: zero once the relationship
+    /// holds, otherwise the gap `ConstraintMode::SoftPenalty` folds into
+    /// `cost` - `|actual - computed|` for `Equals`, the amount a `>=`/`<=`
+    /// bound is exceeded otherwise.
+    fn constraint_residual(relationship: RelationshipType, actual: f64, computed: f64) -> f64 {
+        match relationship {
+            RelationshipType::Equals => (actual - computed).abs(),
+            RelationshipType::GreaterThanOrEqual | RelationshipType::GreaterThan => {
+                (computed - actual).max(0.0)
+            }
+            RelationshipType::LessThanOrEqual | RelationshipType::LessThan => {
+                (actual - computed).max(0.0)
+            }
+        }
+    }
+
+    /// `weight * sum(residual^2)` over every constraint under
+    /// `ConstraintMode::SoftPenalty`, zero under `HardProject` (the
+    /// projection already keeps residuals at zero). Folded into `cost` by
+    /// `cost_from_metrics`.
+    fn soft_constraint_penalty(&self, params: &[f64]) -> f64 {
+        let ConstraintMode::SoftPenalty { weight, .. } = self.constraint_mode else {
+            return 0.0;
+        };
+        if self.constraints.is_empty() {
+            return 0.0;
+        }
+
+        let Ok(computed) = self.cached_constraint_values(params) else {
+            return 0.0;
+        };
+
+        let penalty: f64 = self
+            .constraints
+            .iter()
+            .zip(computed.iter())
+            .map(|(constraint, &computed)| {
+                let residual =
+                    Self::constraint_residual(constraint.relationship, params[constraint.target_idx], computed);
+                residual * residual
+            })
+            .sum();
+
+        weight * penalty
+    }
+
     /// Evaluate all constraints (helper for apply_constraints)
     fn evaluate_all_constraints(&self, params: &[f64]) -> Result<Vec<f64>, String> {
         let mut results = Vec::with_capacity(self.constraints.len());
@@ -556,29 +1466,54 @@ impl Problem for CircuitProblem {
     fn cost(&self, params: &[f64]) -> Result<f64, String> {
         // Run simulation with updated parameters
         self.update_parameters(params)?;
-        self.execute_measurements()?;
-        let metrics = self.extract_metrics()?;
 
-        // Compute weighted cost from all targets
-        let total_cost = self
-            .targets
+        if self.corners.is_empty() {
+            self.execute_measurements()?;
+            let metrics = self.extract_metrics()?;
+            return Ok(self.cost_from_metrics(&metrics, params));
+        }
+
+        self.cost_across_corners(params)
+    }
+
+    fn cost_batch(&self, candidates: &[Vec<f64>]) -> Result<Vec<f64>, String> {
+        let pool = match self.worker_pool.as_ref() {
+            Some(pool) => pool,
+            // No worker pool attached: fall back to costing candidates one
+            // at a time on the single in-process NgSpice instance, same as
+            // the trait default, so solvers can always call `cost_batch`
+            // regardless of whether `with_worker_pool()` was used.
+            None => return candidates.iter().map(|params| self.cost(params)).collect(),
+        };
+
+        let netlists: Vec<Vec<String>> = candidates
             .iter()
-            .filter_map(|target| {
-                metrics.get(&target.metric).map(|&value| {
-                    let error = match target.mode {
-                        TargetMode::Min if value >= target.value => value - target.value,
-                        TargetMode::Max if value <= target.value => target.value - value,
-                        TargetMode::Target => (value - target.value).abs(),
-                        _ => 0.0, // Target satisfied
-                    };
-                    error * target.weight
-                })
+            .map(|params| self.render_netlist_for(params))
+            .collect();
+
+        pool.run_batch(&netlists)
+            .into_iter()
+            .zip(candidates.iter())
+            .map(|(output, params)| {
+                let output = output?;
+                let metrics = self.extract_metrics_from(&output);
+                Ok(self.cost_from_metrics(&metrics, params))
             })
-            .sum();
-
-        Ok(total_cost)
+            .collect()
     }
 
+    // No `submit_batch` override: an earlier version of this ran the
+    // worker-pool batch on a detached background thread carrying a raw
+    // `*const CircuitProblem`, which is unsound (the thread outlives the
+    // `&self` borrow it was built from whenever a caller drops the
+    // `BatchHandle` without joining it, e.g. a solver bailing out on error)
+    // and, since nothing actually called `submit_batch` on a
+    // `CircuitProblem` (only `PolishingSolver` forwards it pass-through),
+    // there was no real caller to make that unsafety worth it. `cost_batch`
+    // above already does the real concurrency (fanning candidates out
+    // across `worker_pool`); the trait's default `submit_batch` wraps it in
+    // an already-finished `BatchHandle` for free.
+
     fn num_params(&self) -> usize {
         self.params.len()
     }
@@ -591,73 +1526,139 @@ impl Problem for CircuitProblem {
         &self.bounds
     }
 
+    fn lower_bounds(&self) -> Option<Vec<f64>> {
+        Some(self.bounds.iter().map(|&(min, _)| min).collect())
+    }
+
+    fn upper_bounds(&self) -> Option<Vec<f64>> {
+        Some(self.bounds.iter().map(|&(_, max)| max).collect())
+    }
+
+    /// Project `params[target_idx]` onto `relationship` against `computed`,
+    /// clamped to `[min, max]`. On `final_pass`, `GreaterThan`/`LessThan`
+    /// round their result *away* from `computed` to the next grid point (up
+    /// for `GreaterThan`, down for `LessThan`) instead of to nearest, so the
+    /// strict inequality still holds once [`round_to_precision`] runs.
+    fn project_relationship(
+        relationship: RelationshipType,
+        current: f64,
+        computed: f64,
+        min: f64,
+        max: f64,
+        final_pass: bool,
+    ) -> f64 {
+        let projected = match relationship {
+            RelationshipType::Equals => computed,
+            RelationshipType::GreaterThanOrEqual if current < computed => computed,
+            RelationshipType::LessThanOrEqual if current > computed => computed,
+            RelationshipType::GreaterThan if current <= computed => {
+                if final_pass {
+                    (computed * SKY130_GRID_INV).ceil() * SKY130_GRID_SIZE + SKY130_GRID_SIZE
+                } else {
+                    computed + 1e-6
+                }
+            }
+            RelationshipType::LessThan if current >= computed => {
+                if final_pass {
+                    (computed * SKY130_GRID_INV).floor() * SKY130_GRID_SIZE - SKY130_GRID_SIZE
+                } else {
+                    computed - 1e-6
+                }
+            }
+            _ => current, // Constraint already satisfied
+        };
+        projected.clamp(min, max)
+    }
+
+    /// Drive relationship projection and grid snapping to a joint fixed
+    /// point instead of applying each once: projecting a `target_idx` onto
+    /// its relationship can move a param that another constraint reads as a
+    /// source, and snapping every param to the Sky130 grid afterward can
+    /// itself push a just-projected value back across its relationship
+    /// boundary. Each pass re-evaluates every `computed` value from the
+    /// *current* params, re-projects, and re-snaps, stopping once no param
+    /// moves by more than half a grid step (or `MAX_FIXED_POINT_ITERS` is
+    /// hit, which means the constraint set isn't satisfiable on this grid).
     fn apply_constraints(&self, params: &mut [f64]) -> Result<(), String> {
-        // Fast path: no constraints, just round to Sky130 grid
+        const MAX_FIXED_POINT_ITERS: u32 = 16;
+
+        // Fast path: no relationship constraints, just round to native precision
         if self.constraints.is_empty() {
-            for param in params.iter_mut() {
-                *param = (*param * SKY130_GRID_INV).round() * SKY130_GRID_SIZE;
+            for (param, &integer) in params.iter_mut().zip(self.is_integer.iter()) {
+                *param = round_to_precision(*param, integer);
+            }
+            if matches!(self.feasibility_strategy, Some(FeasibilityStrategy::Repair)) {
+                self.repair_toward_feasible(params);
             }
             return Ok(());
         }
 
-        // Evaluate constraints (with caching)
-        let constraint_results = {
-            // Compute hash of source parameters only
-            let mut hasher = std::collections::hash_map::DefaultHasher::new();
-            for (i, &param) in params.iter().enumerate() {
-                if self
-                    .constraints
-                    .iter()
-                    .any(|c| c.source_indices.contains(&i))
-                {
-                    std::hash::Hash::hash(&param.to_bits(), &mut hasher);
+        let hard_project = matches!(self.constraint_mode, ConstraintMode::HardProject);
+        let mut converged = false;
+
+        for iter in 0..MAX_FIXED_POINT_ITERS {
+            let final_pass = iter == MAX_FIXED_POINT_ITERS - 1;
+            let before = params.to_vec();
+
+            // (a) Recompute every `computed` value from the current params.
+            let constraint_results = self.evaluate_all_constraints(params)?;
+
+            // (b) Apply each relationship projection - only under
+            // HardProject; SoftPenalty leaves params[target_idx] alone and
+            // lets cost_from_metrics fold the residual into the cost instead.
+            if hard_project {
+                for (constraint, &computed) in self.constraints.iter().zip(constraint_results.iter()) {
+                    let target_idx = constraint.target_idx;
+                    let (min, max) = self.bounds[target_idx];
+                    params[target_idx] = Self::project_relationship(
+                        constraint.relationship,
+                        params[target_idx],
+                        computed,
+                        min,
+                        max,
+                        final_pass,
+                    );
                 }
             }
-            let param_hash = std::hash::Hasher::finish(&hasher);
 
-            // Check cache
-            let mut cache = self.constraint_cache.borrow_mut();
-            if let Some((cached_hash, cached_results)) = cache.take() {
-                if cached_hash == param_hash {
-                    cached_results // Cache hit - steal results
-                } else {
-                    // Cache miss: evaluate all constraints
-                    let results = self.evaluate_all_constraints(params)?;
-                    *cache = Some((param_hash, results.clone()));
-                    results
-                }
-            } else {
-                // No cache: evaluate all constraints
-                let results = self.evaluate_all_constraints(params)?;
-                *cache = Some((param_hash, results.clone()));
-                results
+            // (c) Snap every param to its native precision.
+            for (param, &integer) in params.iter_mut().zip(self.is_integer.iter()) {
+                *param = round_to_precision(*param, integer);
             }
-        };
-
-        // Apply constraint results to parameters
-        for (constraint, &computed) in self.constraints.iter().zip(constraint_results.iter()) {
-            let target_idx = constraint.target_idx;
-            let (min, max) = self.bounds[target_idx];
-            let current = params[target_idx];
 
-            params[target_idx] = match constraint.relationship {
-                RelationshipType::Equals => computed,
-                RelationshipType::GreaterThanOrEqual if current < computed => computed,
-                RelationshipType::LessThanOrEqual if current > computed => computed,
-                RelationshipType::GreaterThan if current <= computed => computed + 1e-6,
-                RelationshipType::LessThan if current >= computed => computed - 1e-6,
-                _ => current, // Constraint already satisfied
+            let moved = before
+                .iter()
+                .zip(params.iter())
+                .any(|(old, new)| (old - new).abs() > SKY130_GRID_SIZE / 2.0);
+            if !moved {
+                converged = true;
+                break;
             }
-            .clamp(min, max);
         }
 
-        // Round all params to Sky130 grid
-        for param in params.iter_mut() {
-            *param = (*param * SKY130_GRID_INV).round() * SKY130_GRID_SIZE;
+        // Refresh the constraint cache with the params we actually settled
+        // on, so `cost`'s soft-penalty residual path reads the post-fixed-
+        // point values rather than recomputing against a stale cache entry.
+        self.cached_constraint_values(params)?;
+
+        if matches!(self.feasibility_strategy, Some(FeasibilityStrategy::Repair)) {
+            self.repair_toward_feasible(params);
+        }
+
+        if !converged && hard_project {
+            return Err(format!(
+                "constraint projection did not converge to a fixed point on the Sky130 grid within {} iterations - this constraint set is infeasible at {}nm resolution",
+                MAX_FIXED_POINT_ITERS,
+                SKY130_GRID_SIZE * 1e9
+            ));
         }
 
         Ok(())
     }
+
+    fn max_constraint_violation(&self, params: &[f64]) -> f64 {
+        crate::core::max_violation(&self.feasibility_constraints, params)
+    }
 }
 
 impl Drop for CircuitProblem {
@@ -775,13 +1776,17 @@ impl CircuitOptimizationCallback {
 }
 
 impl OptimizationCallback for CircuitOptimizationCallback {
-    fn on_iteration(&mut self, iteration: u32, params: &[f64], cost: f64) -> Result<(), String> {
-        Python::with_gil(|py| {
-            if py.check_signals().is_err() {
-                return Err("Interrupted by user (Ctrl+C)".to_string());
-            }
-            Ok(())
-        })?;
+    fn on_iteration(
+        &mut self,
+        iteration: u32,
+        params: &[f64],
+        cost: f64,
+    ) -> std::ops::ControlFlow<StopReason, ()> {
+        if Python::with_gil(|py| py.check_signals()).is_err() {
+            return std::ops::ControlFlow::Break(StopReason::UserInterrupt(
+                "Interrupted by user (Ctrl+C)".to_string(),
+            ));
+        }
 
         self.iteration_count = iteration;
 
@@ -799,13 +1804,13 @@ impl OptimizationCallback for CircuitOptimizationCallback {
                 let problem = &*self.problem;
                 problem.print_ngspice_output();
             }
-            return Err(e);
+            return std::ops::ControlFlow::Break(StopReason::SimulationError(e));
         }
 
-        Ok(())
-    }
+        if self.iteration_count >= self.max_iterations {
+            return std::ops::ControlFlow::Break(StopReason::MaxIterations);
+        }
 
-    fn should_stop(&self) -> bool {
-        self.iteration_count >= self.max_iterations
+        std::ops::ControlFlow::Continue(())
     }
 }
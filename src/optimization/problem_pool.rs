@@ -0,0 +1,54 @@
+//! Arc-owned pooled metric evaluation for callers that used to reach into a
+//! `CircuitProblem` through an unsafe raw pointer (see
+//! [`CircuitOptimizationCallback`](super::callback::CircuitOptimizationCallback)).
+//!
+//! This deliberately does *not* clone `CircuitProblem` itself and run
+//! `update_parameters`/`execute_measurements`/`extract_metrics` concurrently
+//! across worker threads: `NgSpice` is a single shared-library instance per
+//! process (see [`crate::optimization::worker_pool`]'s module doc), so two
+//! threads driving that interactive path at once would race on its global
+//! state. Instead `ProblemPool` reuses the same render-netlist ->
+//! subprocess-worker-pool -> extract-metrics path `Problem::cost_batch`
+//! already takes, just returning the full metric map instead of a derived
+//! cost scalar, and holds the problem behind an `Arc` so it can be shared
+//! across threads safely instead of through a raw pointer.
+
+use super::problem::CircuitProblem;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Evaluates batches of candidate parameter sets against a shared,
+/// `Arc`-owned [`CircuitProblem`], fanning each batch across the problem's
+/// worker pool instead of evaluating one candidate at a time.
+pub struct ProblemPool {
+    problem: Arc<CircuitProblem>,
+}
+
+impl ProblemPool {
+    /// Wrap `problem` for pooled evaluation. Fails if `problem` wasn't built
+    /// with [`CircuitProblem::with_worker_pool`], since there would be
+    /// nothing to fan the batch across.
+    pub fn new(problem: Arc<CircuitProblem>) -> Result<Self, String> {
+        if !problem.has_worker_pool() {
+            return Err(
+                "ProblemPool requires a problem built with with_worker_pool()".to_string(),
+            );
+        }
+        Ok(Self { problem })
+    }
+
+    /// The pooled problem, for callers that also need direct access (e.g.
+    /// to read bounds or param names alongside evaluating a batch).
+    pub fn problem(&self) -> &Arc<CircuitProblem> {
+        &self.problem
+    }
+
+    /// Evaluate every candidate in `candidates` across the pool, returning
+    /// each one's extracted metrics (or its own simulation error) in
+    /// submission order.
+    pub fn evaluate_batch(&self, candidates: &[Vec<f64>]) -> Vec<Result<HashMap<String, f64>, String>> {
+        self.problem
+            .metrics_batch(candidates)
+            .expect("ProblemPool::new already verified a worker pool is attached")
+    }
+}
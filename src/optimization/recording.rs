@@ -0,0 +1,129 @@
+//! Built-in [`OptimizationCallback`] that captures a solver's trajectory in
+//! memory, for callers who just want a portable report at the end of the
+//! run instead of standing up a [`super::observer::RunObserver`] sink.
+
+use super::observer::{json_number_array, json_string_array};
+use super::solvers::traits::{OptimizationCallback, SolverResult, StopReason};
+use std::ops::ControlFlow;
+
+/// One recorded step: the solver's reported params/cost at that iteration,
+/// alongside the best cost seen by any iteration up to and including it.
+#[derive(Debug, Clone)]
+pub struct RecordedIteration {
+    pub iteration: u32,
+    pub params: Vec<f64>,
+    pub cost: f64,
+    pub best_cost: f64,
+}
+
+/// Captures `(iteration, params, cost)` every step a [`Solver`](super::solvers::traits::Solver)
+/// reports through [`OptimizationCallback::on_iteration`], and can emit the
+/// captured trajectory as a Markdown report, CSV, or JSON - useful for
+/// diffing solver runs or sharing a reproducible convergence log. Never
+/// asks the solver to stop early; wrap it with whatever stop criteria the
+/// run actually needs.
+pub struct RecordingCallback {
+    history: Vec<RecordedIteration>,
+    best_cost: f64,
+}
+
+impl Default for RecordingCallback {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RecordingCallback {
+    pub fn new() -> Self {
+        Self {
+            history: Vec::new(),
+            best_cost: f64::INFINITY,
+        }
+    }
+
+    /// The full recorded trajectory, in iteration order.
+    pub fn history(&self) -> &[RecordedIteration] {
+        &self.history
+    }
+
+    /// Markdown report: an iteration/cost/best-so-far table, plus a summary
+    /// block built from the solver's final [`SolverResult`].
+    pub fn to_markdown(&self, solver_name: &str, result: &SolverResult) -> String {
+        let mut out = String::new();
+        out.push_str(&format!("# {} run report\n\n", solver_name));
+        out.push_str("| iteration | cost | best so far |\n");
+        out.push_str("|---:|---:|---:|\n");
+        for rec in &self.history {
+            out.push_str(&format!(
+                "| {} | {:e} | {:e} |\n",
+                rec.iteration, rec.cost, rec.best_cost
+            ));
+        }
+        out.push_str("\n## Summary\n\n");
+        out.push_str(&format!("- Solver: {}\n", solver_name));
+        out.push_str(&format!("- Final cost: {:e}\n", result.cost));
+        out.push_str(&format!("- Iterations: {}\n", result.iterations));
+        out.push_str(&format!("- Cost evaluations: {}\n", result.cost_evals));
+        out.push_str(&format!("- Gradient evaluations: {}\n", result.grad_evals));
+        out.push_str(&format!("- Message: {}\n", result.message));
+        out
+    }
+
+    /// CSV dump of the trajectory: `iteration,cost,best_cost,<param_names...>`.
+    pub fn to_csv(&self, param_names: &[String]) -> String {
+        let mut out = String::new();
+        out.push_str("iteration,cost,best_cost");
+        for name in param_names {
+            out.push(',');
+            out.push_str(name);
+        }
+        out.push('\n');
+
+        for rec in &self.history {
+            out.push_str(&format!("{},{:e},{:e}", rec.iteration, rec.cost, rec.best_cost));
+            for &value in &rec.params {
+                out.push_str(&format!(",{:e}", value));
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Newline-delimited JSON dump of the trajectory, one object per
+    /// iteration with `iteration`, `cost`, `best_cost`, `param_names` and
+    /// `params` keys.
+    pub fn to_json(&self, param_names: &[String]) -> String {
+        let mut out = String::new();
+        for rec in &self.history {
+            out.push_str(&format!(
+                "{{\"iteration\":{},\"cost\":{},\"best_cost\":{},\"param_names\":{},\"params\":{}}}\n",
+                rec.iteration,
+                rec.cost,
+                rec.best_cost,
+                json_string_array(param_names.iter().map(|s| s.as_str())),
+                json_number_array(rec.params.iter().copied())
+            ));
+        }
+        out
+    }
+}
+
+impl OptimizationCallback for RecordingCallback {
+    fn on_iteration(
+        &mut self,
+        iteration: u32,
+        params: &[f64],
+        cost: f64,
+    ) -> ControlFlow<StopReason, ()> {
+        if cost < self.best_cost {
+            self.best_cost = cost;
+        }
+        self.history.push(RecordedIteration {
+            iteration,
+            params: params.to_vec(),
+            cost,
+            best_cost: self.best_cost,
+        });
+        ControlFlow::Continue(())
+    }
+}
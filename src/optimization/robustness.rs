@@ -0,0 +1,148 @@
+//! Counterexample-minimizing robustness analysis, borrowing "shrinking"
+//! from property-based testing: once an optimization has converged,
+//! perturb the optimum within a supplied per-parameter `+/-` range looking
+//! for a combination that violates a target, then bisect the failing
+//! vector back toward nominal - one coordinate at a time - until no
+//! further reduction still fails. The result is the minimal,
+//! human-interpretable corner that breaks each target, reported through
+//! the same [`RunObserver`] sinks a normal optimization run uses, so
+//! designers get a yield/sensitivity report instead of just a pass/fail.
+
+use super::observer::{RunObserver, RunSummary};
+use super::problem::CircuitProblem;
+use super::solvers::traits::Problem;
+use rand::Rng;
+
+/// A perturbation of the optimum that violates `metric`'s spec, already
+/// shrunk to (approximately) the smallest deviation that still fails.
+#[derive(Debug, Clone)]
+pub struct Counterexample {
+    pub metric: String,
+    pub param_names: Vec<String>,
+    /// Parameter values at the minimal failing point.
+    pub params: Vec<f64>,
+    /// `params[i] - nominal[i]`, the deviation that breaks the spec.
+    pub deltas: Vec<f64>,
+    pub achieved: f64,
+}
+
+/// Search for a failing corner per target around `nominal`, then shrink it.
+///
+/// `ranges[i]` is the `+/-` perturbation allowed on parameter `i`; `samples`
+/// bounds how many random perturbations are tried per target before giving
+/// up. Every search step is reported through `observers` (as a normal
+/// iteration), and each confirmed counterexample is reported as a run
+/// (`success: false`, `stop_reason` naming the violated target) so existing
+/// CSV/JSONL/SQLite sinks capture the sweep without new plumbing.
+pub fn find_counterexamples(
+    problem: &CircuitProblem,
+    nominal: &[f64],
+    ranges: &[f64],
+    samples: usize,
+    observers: &mut [Box<dyn RunObserver>],
+) -> Result<Vec<Counterexample>, String> {
+    let param_names = problem.param_names().to_vec();
+    let bounds = problem.bounds().to_vec();
+    let mut rng = rand::thread_rng();
+    let mut counterexamples = Vec::new();
+
+    for target in problem.targets().to_vec() {
+        let mut failing: Option<Vec<f64>> = None;
+        let mut step = 0u32;
+
+        for _ in 0..samples {
+            let candidate: Vec<f64> = nominal
+                .iter()
+                .zip(ranges.iter())
+                .zip(bounds.iter())
+                .map(|((&nom, &range), &(lo, hi))| {
+                    let delta = rng.gen_range(-range..=range);
+                    (nom + delta).clamp(lo, hi)
+                })
+                .collect();
+
+            let solution = problem.evaluate_full(&candidate)?;
+            step += 1;
+            if let Some(report) = solution.target(&target.metric) {
+                notify_step(observers, step, &candidate, report.achieved);
+                if !report.satisfied {
+                    failing = Some(candidate);
+                    break;
+                }
+            }
+        }
+
+        let Some(mut failing) = failing else {
+            continue;
+        };
+
+        // Shrink: bisect each coordinate's distance to nominal, keeping the
+        // reduction only if the target still fails. Repeat full passes
+        // until one changes nothing - a fixed point.
+        loop {
+            let mut changed = false;
+
+            for i in 0..failing.len() {
+                let original = failing[i];
+                let shrunk = nominal[i] + (original - nominal[i]) / 2.0;
+                if (shrunk - original).abs() < 1e-12 {
+                    continue;
+                }
+
+                failing[i] = shrunk;
+                let solution = problem.evaluate_full(&failing)?;
+                step += 1;
+                let report = solution.target(&target.metric);
+                let still_fails = report.map(|r| !r.satisfied).unwrap_or(false);
+                if let Some(report) = report {
+                    notify_step(observers, step, &failing, report.achieved);
+                }
+
+                if still_fails {
+                    changed = true;
+                } else {
+                    failing[i] = original;
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        let solution = problem.evaluate_full(&failing)?;
+        let achieved = solution.target(&target.metric).map(|r| r.achieved).unwrap_or(0.0);
+        let deltas: Vec<f64> = failing.iter().zip(nominal.iter()).map(|(&f, &n)| f - n).collect();
+
+        let summary = RunSummary {
+            success: false,
+            stop_reason: format!("minimal counterexample for '{}'", target.metric),
+            iterations: step,
+            final_cost: achieved,
+            param_names: param_names.clone(),
+            final_params: failing.clone(),
+        };
+        for observer in observers.iter_mut() {
+            observer.observe_final(&summary);
+        }
+
+        counterexamples.push(Counterexample {
+            metric: target.metric.clone(),
+            param_names: param_names.clone(),
+            params: failing,
+            deltas,
+            achieved,
+        });
+    }
+
+    Ok(counterexamples)
+}
+
+/// Report one search step (random probe or shrink bisection) as a normal
+/// iteration, so progress streams through the observer sinks like any other
+/// run.
+fn notify_step(observers: &mut [Box<dyn RunObserver>], step: u32, params: &[f64], achieved: f64) {
+    for observer in observers.iter_mut() {
+        observer.observe_iter(step, params, achieved, &[]);
+    }
+}
@@ -1,11 +1,112 @@
-use super::traits::{OptimizationCallback, Problem, Solver, SolverResult};
+use super::constraint_penalty::{self, ConstraintPenalty};
+use super::sampling::{latin_hypercube, InitMode};
+use super::traits::{OptimizationCallback, Problem, Solver, SolverResult, StopReason};
 use rand_distr::{Distribution, StandardNormal};
+use std::ops::ControlFlow;
+
+/// Floor applied to eigenvalues after [`eigen_decompose`] so a collapsing
+/// covariance direction never produces a negative or zero step.
+const EIGENVALUE_FLOOR: f64 = 1e-20;
+
+/// Symmetric Jacobi eigenvalue iteration on the small `n x n` covariance
+/// matrix: cyclic sweeps over every off-diagonal pair, zeroing each via a
+/// Givens rotation, until the matrix is (numerically) diagonal. Cheap and robust for
+/// the sizes CMA-ES actually needs (tens of parameters), unlike general
+/// (non-symmetric) eigensolvers that would need pivoting/iteration tricks.
+///
+/// Returns `(B, D)` where `B`'s columns are the orthonormal eigenvectors and
+/// `D` holds the eigenvalue square roots (floored at [`EIGENVALUE_FLOOR`]),
+/// ready to use directly in `y = B * (D ⊙ z)`.
+fn eigen_decompose(c: &[Vec<f64>], n: usize) -> (Vec<Vec<f64>>, Vec<f64>) {
+    // Force exact symmetry - floating point drift in the rank-one/rank-mu
+    // update can otherwise leave C[i][j] != C[j][i] by a tiny amount.
+    let mut a = vec![vec![0.0_f64; n]; n];
+    for i in 0..n {
+        for j in 0..n {
+            a[i][j] = 0.5 * (c[i][j] + c[j][i]);
+        }
+    }
+
+    let mut v = vec![vec![0.0_f64; n]; n];
+    for i in 0..n {
+        v[i][i] = 1.0;
+    }
+
+    const MAX_SWEEPS: usize = 60;
+    for _ in 0..MAX_SWEEPS {
+        let mut off_diag_sq = 0.0;
+        for i in 0..n {
+            for j in 0..n {
+                if i != j {
+                    off_diag_sq += a[i][j] * a[i][j];
+                }
+            }
+        }
+        if off_diag_sq < 1e-24 {
+            break;
+        }
+
+        for p in 0..n.saturating_sub(1) {
+            for q in (p + 1)..n {
+                if a[p][q].abs() < 1e-300 {
+                    continue;
+                }
+
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t = if theta == 0.0 {
+                    1.0
+                } else {
+                    theta.signum() / (theta.abs() + (1.0 + theta * theta).sqrt())
+                };
+                let cos = 1.0 / (1.0 + t * t).sqrt();
+                let sin = t * cos;
+
+                let app = a[p][p];
+                let aqq = a[q][q];
+                let apq = a[p][q];
+                a[p][p] = cos * cos * app - 2.0 * sin * cos * apq + sin * sin * aqq;
+                a[q][q] = sin * sin * app + 2.0 * sin * cos * apq + cos * cos * aqq;
+                a[p][q] = 0.0;
+                a[q][p] = 0.0;
+
+                for i in 0..n {
+                    if i != p && i != q {
+                        let aip = a[i][p];
+                        let aiq = a[i][q];
+                        a[i][p] = cos * aip - sin * aiq;
+                        a[p][i] = a[i][p];
+                        a[i][q] = sin * aip + cos * aiq;
+                        a[q][i] = a[i][q];
+                    }
+                }
+                for i in 0..n {
+                    let vip = v[i][p];
+                    let viq = v[i][q];
+                    v[i][p] = cos * vip - sin * viq;
+                    v[i][q] = sin * vip + cos * viq;
+                }
+            }
+        }
+    }
+
+    let d: Vec<f64> = (0..n).map(|i| a[i][i].max(EIGENVALUE_FLOOR).sqrt()).collect();
+    (v, d)
+}
 
 pub struct CMAESOptimizer {
     max_iter: u32,
     precision: f64,
     population_size: usize,
     sigma: f64,
+    /// Caps the rayon thread pool each generation's `cost_batch` call is run
+    /// under, see [`CMAESOptimizer::with_workers`]. `None` uses rayon's
+    /// global pool (defaults to one thread per core).
+    threads: Option<usize>,
+    /// How the initial mean is chosen, see [`CMAESOptimizer::with_init`].
+    init_mode: InitMode,
+    /// Added to each offspring's cost once computed, see
+    /// [`CMAESOptimizer::with_constraint_penalty`].
+    constraint_penalty: Option<ConstraintPenalty>,
 }
 
 impl CMAESOptimizer {
@@ -15,6 +116,9 @@ impl CMAESOptimizer {
             precision,
             population_size: 0,
             sigma: 0.3,
+            threads: None,
+            init_mode: InitMode::Uniform,
+            constraint_penalty: None,
         }
     }
 
@@ -28,6 +132,38 @@ impl CMAESOptimizer {
         self
     }
 
+    /// Cap generation cost evaluation to `n` concurrent threads instead of
+    /// rayon's default of one per core - useful to bound how many simulator
+    /// instances a noisy-cost [`Problem`] ends up running at once. Only
+    /// affects problems whose `cost_batch` actually parallelizes over rayon
+    /// (the trait default); [`crate::optimization::problem::CircuitProblem`]
+    /// fans out across its own subprocess `WorkerPool` instead, sized by
+    /// `with_worker_pool`.
+    pub fn with_workers(mut self, n: usize) -> Self {
+        self.threads = Some(n.max(1));
+        self
+    }
+
+    /// Configure how the initial mean is chosen (default: `Uniform`, i.e.
+    /// `problem.initial_params()` unchanged). `LatinHypercube` instead draws
+    /// a `lambda`-sized Latin Hypercube sample, costs it, and starts the
+    /// mean at its best point - CMA-ES only ever maintains one mean, so
+    /// unlike `ParticleOptimizer`/`DifferentialEvolutionOptimizer` this
+    /// trades the starting guess for better initial coverage rather than
+    /// replacing an ongoing population.
+    pub fn with_init(mut self, mode: InitMode) -> Self {
+        self.init_mode = mode;
+        self
+    }
+
+    /// Fold a [`ConstraintPenalty`] into every offspring's cost once it's
+    /// computed, so `ParameterConstraint` relationships the problem doesn't
+    /// hard-project influence the mean shift and rank-μ update.
+    pub fn with_constraint_penalty(mut self, penalty: ConstraintPenalty) -> Self {
+        self.constraint_penalty = Some(penalty);
+        self
+    }
+
     #[inline]
     fn clamp_params(&self, params: &mut [f64], bounds: &[(f64, f64)]) {
         for (i, &(min, max)) in bounds.iter().enumerate() {
@@ -50,6 +186,16 @@ impl Solver for CMAESOptimizer {
         let bounds = problem.bounds();
         let mut rng = rand::thread_rng();
 
+        let thread_pool = match self.threads {
+            Some(n) => Some(
+                rayon::ThreadPoolBuilder::new()
+                    .num_threads(n)
+                    .build()
+                    .map_err(|e| format!("Failed to build thread pool: {}", e))?,
+            ),
+            None => None,
+        };
+
         // Set population size if not specified
         if self.population_size == 0 {
             self.population_size = 4 + (3.0 * (n as f64).ln()).floor() as usize;
@@ -58,8 +204,30 @@ impl Solver for CMAESOptimizer {
         let lambda = self.population_size;
         let mu = lambda / 2;
 
-        // Initialize mean
-        let mut mean = problem.initial_params().to_vec();
+        let mut cost_evals = 0;
+
+        // Initialize mean: either the problem's starting point, or the best
+        // of a Latin Hypercube sample over the whole bounded space.
+        let mut mean = match self.init_mode {
+            InitMode::Uniform => problem.initial_params().to_vec(),
+            InitMode::LatinHypercube => {
+                let mut candidates = latin_hypercube(lambda, bounds, &mut rng);
+                for candidate in candidates.iter_mut() {
+                    self.clamp_params(candidate, bounds);
+                    problem.apply_constraints(candidate)?;
+                }
+                let mut costs = match &thread_pool {
+                    Some(pool) => pool.install(|| problem.cost_batch(&candidates))?,
+                    None => problem.cost_batch(&candidates)?,
+                };
+                constraint_penalty::apply_to_batch(self.constraint_penalty.as_ref(), &mut costs, &candidates);
+                cost_evals += costs.len();
+                let best = (0..lambda)
+                    .min_by(|&a, &b| costs[a].partial_cmp(&costs[b]).unwrap())
+                    .unwrap();
+                candidates[best].clone()
+            }
+        };
 
         // Covariance matrix - explicitly typed as f64
         let mut C: Vec<Vec<f64>> = vec![vec![0.0_f64; n]; n];
@@ -67,6 +235,11 @@ impl Solver for CMAESOptimizer {
             C[i][i] = 1.0;
         }
 
+        // Factorization C = B * D^2 * B^T, kept in sync with `C` and
+        // refreshed every `refactor_every` generations (full decomposition
+        // is only O(n^3) per refresh, not per offspring).
+        let (mut b_matrix, mut d_diag) = eigen_decompose(&C, n);
+
         // Step size and evolution paths
         let mut sigma = self.sigma;
         let mut ps: Vec<f64> = vec![0.0; n];
@@ -80,6 +253,9 @@ impl Solver for CMAESOptimizer {
         let damps =
             1.0 + 2.0 * (0.0_f64).max((((mu - 1) as f64) / (n as f64 + 1.0)).sqrt() - 1.0) + cs;
 
+        let refactor_every = (1.0_f64.max(1.0 / (c1 + cmu) / (10.0 * n as f64))).round() as u32;
+        let refactor_every = refactor_every.max(1);
+
         // Recombination weights
         let mut weights = vec![0.0; mu];
         for i in 0..mu {
@@ -90,56 +266,76 @@ impl Solver for CMAESOptimizer {
             *w /= sum_weights;
         }
 
-        let mut cost_evals = 0;
+        // Variance-effective selection mass - the number of "effectively
+        // independent" offspring the weighted recombination is equivalent
+        // to. Used in the step-size path's normalization below instead of
+        // the raw count `mu`, which overstates it whenever the weights
+        // taper off rather than being uniform.
+        let mu_eff: f64 = 1.0 / weights.iter().map(|w| w * w).sum::<f64>();
+
         let mut best_cost = f64::INFINITY;
         let mut best_params = mean.clone();
 
         for iter in 0..self.max_iter {
-            // Generate and evaluate population
+            // Sample the whole generation first, then cost it in one batch -
+            // lets a problem with a worker pool fan lambda offspring out
+            // across multiple NgSpice instances instead of costing them
+            // serially, one simulation at a time.
             let mut population = Vec::with_capacity(lambda);
-            let mut costs = Vec::with_capacity(lambda);
+            // y_i = (offspring_i - mean) / sigma before clamping/constraints,
+            // kept so the rank-mu update below can reuse exactly the steps
+            // that were actually sampled.
+            let mut steps = Vec::with_capacity(lambda);
 
             for _ in 0..lambda {
                 // Sample from standard normal
                 let z: Vec<f64> = (0..n).map(|_| StandardNormal.sample(&mut rng)).collect();
 
-                // Transform: y = mean + sigma * C^(1/2) * z
-                // Simplified approach: use diagonal approximation
+                // y = B * (D ⊙ z): rotate the scaled standard-normal draw
+                // into C's eigenbasis, giving a true C^(1/2) transform
+                // instead of a per-element diagonal approximation.
+                let dz: Vec<f64> = (0..n).map(|k| d_diag[k] * z[k]).collect();
+                let y: Vec<f64> = (0..n)
+                    .map(|i| (0..n).map(|k| b_matrix[i][k] * dz[k]).sum::<f64>())
+                    .collect();
+
                 let mut offspring = mean.clone();
                 for i in 0..n {
-                    let mut ci_z = 0.0_f64;
-                    for j in 0..n {
-                        // Use diagonal and near-diagonal elements
-                        let c_ij: f64 = C[i][j];
-                        ci_z += c_ij.abs().sqrt() * z[j];
-                    }
-                    offspring[i] += sigma * ci_z;
+                    offspring[i] += sigma * y[i];
                 }
 
                 self.clamp_params(&mut offspring, bounds);
                 problem.apply_constraints(&mut offspring)?;
 
-                let cost = problem.cost(&offspring)?;
-                cost_evals += 1;
-
                 population.push(offspring);
-                costs.push(cost);
+                steps.push(y);
+            }
 
+            let mut costs = match &thread_pool {
+                Some(pool) => pool.install(|| problem.cost_batch(&population))?,
+                None => problem.cost_batch(&population)?,
+            };
+            constraint_penalty::apply_to_batch(self.constraint_penalty.as_ref(), &mut costs, &population);
+            cost_evals += costs.len();
+
+            for (offspring, &cost) in population.iter().zip(costs.iter()) {
                 if cost < best_cost {
                     best_cost = cost;
-                    best_params = population.last().unwrap().clone();
+                    best_params = offspring.clone();
                 }
             }
 
             // Report best of generation
-            callback.on_iteration(iter + 1, &best_params, best_cost)?;
-
-            if callback.should_stop() {
+            if let ControlFlow::Break(reason) = callback.on_iteration(iter + 1, &best_params, best_cost) {
+                if let StopReason::SimulationError(e) = reason {
+                    return Err(e);
+                }
                 return Ok(SolverResult {
-                    success: true,
+                    success: reason.is_success(),
                     cost: best_cost,
                     iterations: iter + 1,
-                    message: "Stopped by callback".into(),
+                    message: reason.message(),
+                    max_violation: problem.max_constraint_violation(&best_params),
                     params: best_params,
                     cost_evals,
                     grad_evals: 0,
@@ -152,6 +348,7 @@ impl Solver for CMAESOptimizer {
                     cost: best_cost,
                     iterations: iter + 1,
                     message: "Converged".into(),
+                    max_violation: problem.max_constraint_violation(&best_params),
                     params: best_params,
                     cost_evals,
                     grad_evals: 0,
@@ -179,9 +376,23 @@ impl Solver for CMAESOptimizer {
                 .map(|(m, om)| (m - om) / sigma)
                 .collect();
 
+            // Whiten mean_shift through C^-1/2 = B * D^-1 * B^T before
+            // accumulating it into ps - the cs/damps normalization below
+            // assumes ps lives in the isotropic frame where C ≈ I, so
+            // feeding it the raw (anisotropic) mean_shift is only correct
+            // early on, before C has adapted away from the identity.
+            let bt_shift: Vec<f64> = (0..n)
+                .map(|k| (0..n).map(|i| b_matrix[i][k] * mean_shift[i]).sum::<f64>())
+                .collect();
+            let scaled: Vec<f64> = (0..n).map(|k| bt_shift[k] / d_diag[k]).collect();
+            let whitened_shift: Vec<f64> = (0..n)
+                .map(|i| (0..n).map(|k| b_matrix[i][k] * scaled[k]).sum::<f64>())
+                .collect();
+
             // Update ps
             for i in 0..n {
-                ps[i] = (1.0 - cs) * ps[i] + (cs * (2.0 - cs) * mu as f64).sqrt() * mean_shift[i];
+                ps[i] =
+                    (1.0 - cs) * ps[i] + (cs * (2.0 - cs) * mu_eff).sqrt() * whitened_shift[i];
             }
 
             // Adapt sigma
@@ -189,17 +400,35 @@ impl Solver for CMAESOptimizer {
             let expectation_norm = (n as f64).sqrt() * (1.0 - 1.0 / (4.0 * n as f64));
             sigma *= ((cs / damps) * (ps_norm / expectation_norm - 1.0)).exp();
 
-            // Update pc
+            // Update pc - same sqrt(cc*(2-cc)*mu_eff) normalization as ps
+            // above (unwhitened here: pc accumulates in the original
+            // parameter space since it feeds the covariance update below
+            // directly, not a normalized step-size comparison). Plain `cc`
+            // under-scales this by roughly 3-4x for typical cc/mu_eff,
+            // which under-drives the rank-one term `c1*pc[i]*pc[j]` by
+            // about an order of magnitude.
             for i in 0..n {
-                pc[i] = (1.0 - cc) * pc[i] + cc * mean_shift[i];
+                pc[i] = (1.0 - cc) * pc[i] + (cc * (2.0 - cc) * mu_eff).sqrt() * mean_shift[i];
             }
 
-            // Update covariance matrix (rank-one update)
+            // Update covariance matrix: rank-one term from the evolution
+            // path plus the rank-mu term built from the mu best offsprings'
+            // actual sampled steps `y_i`.
             for i in 0..n {
                 for j in 0..n {
-                    C[i][j] = (1.0 - c1 - cmu) * C[i][j] + c1 * pc[i] * pc[j];
+                    let mut rank_mu = 0.0;
+                    for (rank, &idx) in indices[..mu].iter().enumerate() {
+                        rank_mu += weights[rank] * steps[idx][i] * steps[idx][j];
+                    }
+                    C[i][j] = (1.0 - c1 - cmu) * C[i][j] + c1 * pc[i] * pc[j] + cmu * rank_mu;
                 }
             }
+
+            if (iter + 1) % refactor_every == 0 {
+                let (new_b, new_d) = eigen_decompose(&C, n);
+                b_matrix = new_b;
+                d_diag = new_d;
+            }
         }
 
         Ok(SolverResult {
@@ -207,6 +436,7 @@ impl Solver for CMAESOptimizer {
             cost: best_cost,
             iterations: self.max_iter,
             message: "Max iterations reached".into(),
+            max_violation: problem.max_constraint_violation(&best_params),
             params: best_params,
             cost_evals,
             grad_evals: 0,
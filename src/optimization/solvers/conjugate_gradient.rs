@@ -0,0 +1,244 @@
+use super::traits::{OptimizationCallback, Problem, Solver, SolverResult, StopReason};
+use std::ops::ControlFlow;
+
+/// Nonlinear Conjugate Gradient (Polak–Ribière with automatic restarts).
+///
+/// Needs only `O(n)` memory for the current direction/gradient, making it a
+/// lighter-weight alternative to L-BFGS-B for high-dimensional circuit
+/// problems where storing correction pairs is undesirable.
+pub struct ConjugateGradientOptimizer {
+    max_iter: u32,
+    precision: f64,
+    restart_every: u32,
+    armijo_c: f64,
+    backtrack_factor: f64,
+    initial_step: f64,
+    min_step: f64,
+}
+
+impl ConjugateGradientOptimizer {
+    pub fn new(max_iter: u32, precision: f64) -> Self {
+        Self {
+            max_iter,
+            precision,
+            restart_every: 0, // 0 = default to num_params
+            armijo_c: 1e-4,
+            backtrack_factor: 0.5,
+            initial_step: 1.0,
+            min_step: 1e-8,
+        }
+    }
+
+    pub fn with_restart_every(mut self, n: u32) -> Self {
+        self.restart_every = n;
+        self
+    }
+
+    #[inline]
+    fn clamp_params(&self, params: &mut [f64], bounds: &[(f64, f64)]) {
+        for (i, &(min, max)) in bounds.iter().enumerate() {
+            params[i] = params[i].clamp(min, max);
+        }
+    }
+
+    fn gradient(
+        &self,
+        problem: &dyn Problem,
+        x: &[f64],
+        cost_evals: &mut usize,
+    ) -> Result<Vec<f64>, String> {
+        let h = 1e-6;
+        let mut grad = vec![0.0; x.len()];
+        for i in 0..x.len() {
+            let mut plus = x.to_vec();
+            let mut minus = x.to_vec();
+            plus[i] += h;
+            minus[i] -= h;
+            let c_plus = problem.cost(&plus)?;
+            let c_minus = problem.cost(&minus)?;
+            *cost_evals += 2;
+            grad[i] = (c_plus - c_minus) / (2.0 * h);
+        }
+        Ok(grad)
+    }
+
+    /// Armijo backtracking line search along `direction`.
+    fn line_search(
+        &self,
+        problem: &dyn Problem,
+        x: &[f64],
+        f0: f64,
+        grad: &[f64],
+        direction: &[f64],
+        bounds: &[(f64, f64)],
+        cost_evals: &mut usize,
+    ) -> Result<(Vec<f64>, f64, f64), String> {
+        let dir_slope: f64 = grad.iter().zip(direction.iter()).map(|(g, d)| g * d).sum();
+        let mut alpha = self.initial_step;
+
+        for _ in 0..25 {
+            let mut trial: Vec<f64> = x
+                .iter()
+                .zip(direction.iter())
+                .map(|(&xi, &di)| xi + alpha * di)
+                .collect();
+            self.clamp_params(&mut trial, bounds);
+
+            let f1 = problem.cost(&trial)?;
+            *cost_evals += 1;
+
+            if f1 <= f0 + self.armijo_c * alpha * dir_slope {
+                return Ok((trial, f1, alpha));
+            }
+
+            alpha *= self.backtrack_factor;
+            if alpha < self.min_step {
+                break;
+            }
+        }
+
+        let mut trial: Vec<f64> = x
+            .iter()
+            .zip(direction.iter())
+            .map(|(&xi, &di)| xi + alpha * di)
+            .collect();
+        self.clamp_params(&mut trial, bounds);
+        let f1 = problem.cost(&trial)?;
+        *cost_evals += 1;
+        Ok((trial, f1, alpha))
+    }
+}
+
+impl Solver for ConjugateGradientOptimizer {
+    fn name(&self) -> &str {
+        "ConjugateGradient"
+    }
+
+    fn solve(
+        &mut self,
+        problem: &dyn Problem,
+        callback: &mut dyn OptimizationCallback,
+    ) -> Result<SolverResult, String> {
+        let n = problem.num_params();
+        let bounds = problem.bounds();
+        let restart_every = if self.restart_every == 0 {
+            n as u32
+        } else {
+            self.restart_every
+        };
+
+        let mut params = problem.initial_params().to_vec();
+        problem.apply_constraints(&mut params)?;
+
+        let mut cost_evals = 0usize;
+        let mut grad_evals = 0usize;
+
+        let mut cost = problem.cost(&params)?;
+        cost_evals += 1;
+        let mut grad = self.gradient(problem, &params, &mut cost_evals)?;
+        grad_evals += 1;
+        // d_0 = -g_0
+        let mut direction: Vec<f64> = grad.iter().map(|&g| -g).collect();
+
+        for iter in 0..self.max_iter {
+            if let ControlFlow::Break(reason) = callback.on_iteration(iter + 1, &params, cost) {
+                if let StopReason::SimulationError(e) = reason {
+                    return Err(e);
+                }
+                return Ok(SolverResult {
+                    success: reason.is_success(),
+                    cost,
+                    iterations: iter + 1,
+                    message: reason.message(),
+                    max_violation: problem.max_constraint_violation(&params),
+                    params,
+                    cost_evals,
+                    grad_evals,
+                });
+            }
+
+            if cost < self.precision {
+                return Ok(SolverResult {
+                    success: true,
+                    cost,
+                    iterations: iter + 1,
+                    message: "Converged".into(),
+                    max_violation: problem.max_constraint_violation(&params),
+                    params,
+                    cost_evals,
+                    grad_evals,
+                });
+            }
+
+            let (new_params, new_cost, alpha) = self.line_search(
+                problem, &params, cost, &grad, &direction, bounds, &mut cost_evals,
+            )?;
+            if alpha < self.min_step {
+                return Ok(SolverResult {
+                    success: false,
+                    cost,
+                    iterations: iter + 1,
+                    message: "Stagnated (line search failed)".into(),
+                    max_violation: problem.max_constraint_violation(&params),
+                    params,
+                    cost_evals,
+                    grad_evals,
+                });
+            }
+
+            let mut new_params = new_params;
+            problem.apply_constraints(&mut new_params)?;
+
+            let new_grad = self.gradient(problem, &new_params, &mut cost_evals)?;
+            grad_evals += 1;
+
+            // Polak-Ribière coefficient: beta = max(0, g_{k+1}.(g_{k+1}-g_k) / g_k.g_k)
+            let gkgk: f64 = grad.iter().map(|g| g * g).sum();
+            let beta = if gkgk > 1e-300 {
+                let numer: f64 = new_grad
+                    .iter()
+                    .zip(grad.iter())
+                    .map(|(gn, go)| gn * (gn - go))
+                    .sum();
+                (numer / gkgk).max(0.0)
+            } else {
+                0.0
+            };
+
+            let mut new_direction: Vec<f64> = new_grad
+                .iter()
+                .zip(direction.iter())
+                .map(|(g, d)| -g + beta * d)
+                .collect();
+
+            // Restart whenever beta was clamped to zero, the new direction
+            // is not a descent direction, or we hit the periodic cap.
+            let descent: f64 = new_grad
+                .iter()
+                .zip(new_direction.iter())
+                .map(|(g, d)| g * d)
+                .sum();
+            let should_restart =
+                beta == 0.0 || descent >= 0.0 || (iter + 1) % restart_every == 0;
+            if should_restart {
+                new_direction = new_grad.iter().map(|&g| -g).collect();
+            }
+
+            params = new_params;
+            cost = new_cost;
+            grad = new_grad;
+            direction = new_direction;
+        }
+
+        Ok(SolverResult {
+            success: false,
+            cost,
+            iterations: self.max_iter,
+            message: "Max iterations reached".into(),
+            max_violation: problem.max_constraint_violation(&params),
+            params,
+            cost_evals,
+            grad_evals,
+        })
+    }
+}
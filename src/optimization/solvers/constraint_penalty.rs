@@ -0,0 +1,163 @@
+use crate::core::{Parameter, ParameterConstraint, RelationshipType};
+use std::cell::Cell;
+
+/// How [`ConstraintPenalty`]'s coefficient behaves across generations: a
+/// fixed weight, or one that grows generation over generation so early ones
+/// explore freely and later ones are pushed toward feasibility - the same
+/// idea [`crate::optimization::problem::FeasibilityStrategy::AdaptivePenalty`]
+/// uses for CSP-style `Constraint`s, here applied to `ParameterConstraint`'s
+/// derived relationships instead.
+#[derive(Clone, Copy, Debug)]
+pub enum PenaltySchedule {
+    /// `coeff` never changes.
+    Static(f64),
+    /// `coeff` starts at `initial` and is multiplied by `growth` after every
+    /// generation (see [`ConstraintPenalty::step`]), capped at `max`.
+    Adaptive { initial: f64, growth: f64, max: f64 },
+}
+
+/// One `ParameterConstraint`, resolved against a parameter list once up
+/// front so the hot loop never re-derives indices.
+#[derive(Clone)]
+struct ResolvedConstraint {
+    constraint: ParameterConstraint,
+    target_idx: usize,
+    source_indices: Vec<usize>,
+}
+
+/// Folds every unsatisfied [`ParameterConstraint`] relationship into a
+/// penalty a population solver adds on top of a candidate's raw cost,
+/// resolving indices once via `find_target_index`/`find_source_indices` and
+/// evaluating via `evaluate_internal` so the per-candidate hot loop never
+/// round-trips through Python. Attach to a solver with
+/// `.with_constraint_penalty(...)`; [`super::select_solver`] does this
+/// itself when `has_constraints` is set. Cloning copies the coefficient at
+/// its current value, not the schedule's original `initial`. A clone's
+/// `step()` does not advance the original: `CircuitProblem::optimize` hands
+/// each [`crate::optimization::branch_and_bound::solve_mixed_integer`]
+/// relaxation a clone of a penalty that is never stepped itself, so every
+/// relaxation starts the adaptive ramp at `initial`.
+#[derive(Clone)]
+pub struct ConstraintPenalty {
+    constraints: Vec<ResolvedConstraint>,
+    schedule: PenaltySchedule,
+    coeff: Cell<f64>,
+}
+
+impl ConstraintPenalty {
+    /// Resolve `constraints` against `params` (the same parameter list a
+    /// [`crate::optimization::problem::CircuitProblem`] was built from).
+    /// Constraints whose target or source parameters aren't found in
+    /// `params` are dropped rather than erroring, matching how
+    /// `find_source_indices` already degrades (missing sources are simply
+    /// absent from the returned index list).
+    pub fn new(
+        constraints: Vec<ParameterConstraint>,
+        params: &[Parameter],
+        schedule: PenaltySchedule,
+    ) -> Self {
+        let coeff = match schedule {
+            PenaltySchedule::Static(coeff) => coeff,
+            PenaltySchedule::Adaptive { initial, .. } => initial,
+        };
+
+        let resolved = constraints
+            .into_iter()
+            .filter_map(|constraint| {
+                let target_idx = constraint.find_target_index(params)?;
+                let source_indices = constraint.find_source_indices(params);
+                if source_indices.len() != constraint.source_params.len() {
+                    return None;
+                }
+                Some(ResolvedConstraint {
+                    constraint,
+                    target_idx,
+                    source_indices,
+                })
+            })
+            .collect();
+
+        Self {
+            constraints: resolved,
+            schedule,
+            coeff: Cell::new(coeff),
+        }
+    }
+
+    /// Penalty to add to a candidate's cost: `coeff * sum(residual^2)` over
+    /// every resolved constraint, zero once every relationship holds. Reads
+    /// the *current* `coeff` without advancing it - under
+    /// `PenaltySchedule::Adaptive`, every candidate in the same generation
+    /// must see the same `coeff` (see [`ConstraintPenalty::step`]), or
+    /// selection within that generation becomes an artifact of which index a
+    /// candidate happened to land at in the batch rather than its actual
+    /// cost.
+    pub fn penalty(&self, params: &[f64]) -> f64 {
+        if self.constraints.is_empty() {
+            return 0.0;
+        }
+
+        let coeff = self.coeff.get();
+        let sum_sq: f64 = self
+            .constraints
+            .iter()
+            .map(|resolved| {
+                let source_values: Vec<f64> = resolved
+                    .source_indices
+                    .iter()
+                    .map(|&i| params[i])
+                    .collect();
+                let computed = match resolved.constraint.evaluate_internal(&source_values) {
+                    Ok(v) => v,
+                    Err(_) => return 0.0,
+                };
+                let residual =
+                    relationship_residual(resolved.constraint.relationship, params[resolved.target_idx], computed);
+                residual * residual
+            })
+            .sum();
+
+        coeff * sum_sq
+    }
+
+    /// Grow `coeff` one step under `PenaltySchedule::Adaptive` (a no-op
+    /// under `Static`) - called once per generation, after every candidate
+    /// in it has been scored under the same `coeff`, so "later iterations"
+    /// means later generations rather than later candidates within one.
+    pub fn step(&self) {
+        if let PenaltySchedule::Adaptive { growth, max, .. } = self.schedule {
+            self.coeff.set((self.coeff.get() * growth).min(max));
+        }
+    }
+}
+
+/// How far `computed` (the constraint expression's value) falls short of
+/// satisfying `relationship` against `target` (the target parameter's
+/// current value) - zero once the relationship holds. `GreaterThanOrEqual`/
+/// `GreaterThan` require `computed >= target`, so the shortfall is
+/// `max(0, target - computed)`; `LessThanOrEqual`/`LessThan` mirror that in
+/// the other direction, matching `ParameterConstraint::is_satisfied`.
+fn relationship_residual(relationship: RelationshipType, target: f64, computed: f64) -> f64 {
+    match relationship {
+        RelationshipType::Equals => (computed - target).abs(),
+        RelationshipType::GreaterThan | RelationshipType::GreaterThanOrEqual => {
+            (target - computed).max(0.0)
+        }
+        RelationshipType::LessThan | RelationshipType::LessThanOrEqual => {
+            (computed - target).max(0.0)
+        }
+    }
+}
+
+/// Add `penalty`'s per-candidate cost, if attached, to a just-computed batch
+/// of costs in place - the shared tail every population solver's
+/// `cost_batch` call runs through. Every candidate in the batch is scored
+/// under the same `coeff` (one generation == one step of the schedule), via
+/// `penalty()`, before `step()` advances it once for the next generation.
+pub(crate) fn apply_to_batch(penalty: Option<&ConstraintPenalty>, costs: &mut [f64], candidates: &[Vec<f64>]) {
+    let Some(penalty) = penalty else { return };
+    for (cost, candidate) in costs.iter_mut().zip(candidates.iter()) {
+        *cost += penalty.penalty(candidate);
+    }
+    penalty.step();
+}
@@ -0,0 +1,330 @@
+use super::constraint_penalty::{self, ConstraintPenalty};
+use super::sampling::{sample_population, InitMode};
+use super::traits::{OptimizationCallback, Problem, Solver, SolverResult, StopReason};
+use rand::Rng;
+use std::ops::ControlFlow;
+
+/// Which donor vector a mutant is built around, see [`DifferentialEvolutionOptimizer::with_strategy`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Strategy {
+    /// `v = x_best + F*(x_r1 - x_r2)` - biased toward the current best,
+    /// converges faster but is more prone to stalling on multimodal costs.
+    Best1Bin,
+    /// `v = x_r0 + F*(x_r1 - x_r2)` - donor is itself random, keeping the
+    /// population more diverse on rugged landscapes.
+    Rand1Bin,
+}
+
+/// Differential Evolution (Storn-Price) - a population-based, gradient-free
+/// search well suited to noisy, multimodal circuit costs where `CMAESOptimizer`'s
+/// Gaussian sampling can get stuck in a local basin.
+pub struct DifferentialEvolutionOptimizer {
+    max_iter: u32,
+    precision: f64,
+    popsize: usize,
+    strategy: Strategy,
+    /// Mutation scale `F`, or a `(lo, hi)` range to dither it from each
+    /// generation, see [`DifferentialEvolutionOptimizer::with_mutation`].
+    mutation: (f64, f64),
+    recombination: f64,
+    /// Convergence tolerances for the population cost spread, see
+    /// [`DifferentialEvolutionOptimizer::with_convergence_tol`].
+    atol: f64,
+    tol: f64,
+    /// Caps the rayon thread pool each generation's `cost_batch` call is run
+    /// under, see [`DifferentialEvolutionOptimizer::with_workers`]. `None`
+    /// uses rayon's global pool (defaults to one thread per core).
+    threads: Option<usize>,
+    /// How the initial population is seeded, see
+    /// [`DifferentialEvolutionOptimizer::with_init`].
+    init_mode: InitMode,
+    /// Added to each trial's cost once computed, see
+    /// [`DifferentialEvolutionOptimizer::with_constraint_penalty`].
+    constraint_penalty: Option<ConstraintPenalty>,
+}
+
+impl DifferentialEvolutionOptimizer {
+    pub fn new(max_iter: u32, precision: f64) -> Self {
+        Self {
+            max_iter,
+            precision,
+            popsize: 15,
+            strategy: Strategy::Best1Bin,
+            mutation: (0.5, 0.5),
+            recombination: 0.7,
+            atol: 0.0,
+            tol: 0.01,
+            threads: None,
+            init_mode: InitMode::Uniform,
+            constraint_penalty: None,
+        }
+    }
+
+    /// Population size is `popsize * num_params` (default popsize: 15).
+    pub fn with_popsize(mut self, popsize: usize) -> Self {
+        self.popsize = popsize;
+        self
+    }
+
+    /// Donor-construction strategy (default: `best1bin`).
+    pub fn with_strategy(mut self, strategy: Strategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Mutation scale `F`. A single value fixes `F` for the whole run; pass
+    /// a range (e.g. `0.5..1.0`) to dither it, sampling a fresh `F` uniformly
+    /// from the range every generation, which helps escape stagnation
+    /// without hand-tuning a single constant.
+    pub fn with_mutation(mut self, f: impl Into<FRange>) -> Self {
+        self.mutation = f.into().0;
+        self
+    }
+
+    /// Crossover probability `CR` (default: 0.7).
+    pub fn with_recombination(mut self, cr: f64) -> Self {
+        self.recombination = cr;
+        self
+    }
+
+    /// Convergence tolerances: stop once `std(costs) <= atol + tol * mean(costs).abs()`
+    /// (defaults: atol=0.0, tol=0.01).
+    pub fn with_convergence_tol(mut self, atol: f64, tol: f64) -> Self {
+        self.atol = atol;
+        self.tol = tol;
+        self
+    }
+
+    /// Cap generation cost evaluation to `n` concurrent threads instead of
+    /// rayon's default of one per core, see [`super::CMAESOptimizer::with_workers`]
+    /// for the full rationale - the same applies to DE's per-generation
+    /// batch of trial vectors.
+    pub fn with_workers(mut self, n: usize) -> Self {
+        self.threads = Some(n.max(1));
+        self
+    }
+
+    /// Configure how the initial population covers the bounded space
+    /// (default: `Uniform`, i.e. unchanged behavior). `LatinHypercube`
+    /// stratifies each dimension so the first generation can't clump.
+    pub fn with_init(mut self, mode: InitMode) -> Self {
+        self.init_mode = mode;
+        self
+    }
+
+    /// Fold a [`ConstraintPenalty`] into every trial's cost once it's
+    /// computed, so `ParameterConstraint` relationships the problem doesn't
+    /// hard-project influence greedy selection.
+    pub fn with_constraint_penalty(mut self, penalty: ConstraintPenalty) -> Self {
+        self.constraint_penalty = Some(penalty);
+        self
+    }
+
+    #[inline]
+    fn clamp_params(&self, params: &mut [f64], bounds: &[(f64, f64)]) {
+        for (i, &(min, max)) in bounds.iter().enumerate() {
+            params[i] = params[i].clamp(min, max);
+        }
+    }
+
+    /// `count` distinct population indices, all different from `exclude`.
+    fn distinct_indices(&self, popsize: usize, exclude: usize, rng: &mut impl Rng, count: usize) -> Vec<usize> {
+        let mut picked = Vec::with_capacity(count);
+        while picked.len() < count {
+            let idx = rng.gen_range(0..popsize);
+            if idx != exclude && !picked.contains(&idx) {
+                picked.push(idx);
+            }
+        }
+        picked
+    }
+
+    fn sample_f(&self, rng: &mut impl Rng) -> f64 {
+        let (lo, hi) = self.mutation;
+        if lo >= hi {
+            lo
+        } else {
+            rng.gen_range(lo..hi)
+        }
+    }
+}
+
+/// Accepts either a fixed `F` (`From<f64>`) or a dithering `Range<f64>` for
+/// [`DifferentialEvolutionOptimizer::with_mutation`].
+pub struct FRange(pub (f64, f64));
+
+impl From<f64> for FRange {
+    fn from(f: f64) -> Self {
+        FRange((f, f))
+    }
+}
+
+impl From<std::ops::Range<f64>> for FRange {
+    fn from(r: std::ops::Range<f64>) -> Self {
+        FRange((r.start, r.end))
+    }
+}
+
+impl Solver for DifferentialEvolutionOptimizer {
+    fn name(&self) -> &str {
+        "DifferentialEvolution"
+    }
+
+    fn solve(
+        &mut self,
+        problem: &dyn Problem,
+        callback: &mut dyn OptimizationCallback,
+    ) -> Result<SolverResult, String> {
+        let n = problem.num_params();
+        let bounds = problem.bounds();
+        let mut rng = rand::thread_rng();
+
+        let thread_pool = match self.threads {
+            Some(n) => Some(
+                rayon::ThreadPoolBuilder::new()
+                    .num_threads(n)
+                    .build()
+                    .map_err(|e| format!("Failed to build thread pool: {}", e))?,
+            ),
+            None => None,
+        };
+
+        let popsize = (self.popsize * n).max(4);
+
+        // Initialize population within bounds per `init_mode`; the first
+        // member is the provided initial guess, matching how
+        // `ParticleOptimizer` seeds its swarm.
+        let mut population: Vec<Vec<f64>> = Vec::with_capacity(popsize);
+        population.push(problem.initial_params().to_vec());
+        population.extend(sample_population(self.init_mode, popsize - 1, bounds, &mut rng));
+
+        for candidate in population.iter_mut() {
+            self.clamp_params(candidate, bounds);
+            problem.apply_constraints(candidate)?;
+        }
+
+        let mut costs = match &thread_pool {
+            Some(pool) => pool.install(|| problem.cost_batch(&population))?,
+            None => problem.cost_batch(&population)?,
+        };
+        constraint_penalty::apply_to_batch(self.constraint_penalty.as_ref(), &mut costs, &population);
+        let mut cost_evals = costs.len();
+
+        let mut best_idx = (0..popsize)
+            .min_by(|&a, &b| costs[a].partial_cmp(&costs[b]).unwrap())
+            .unwrap();
+
+        for iter in 0..self.max_iter {
+            if let ControlFlow::Break(reason) =
+                callback.on_iteration(iter + 1, &population[best_idx], costs[best_idx])
+            {
+                if let StopReason::SimulationError(e) = reason {
+                    return Err(e);
+                }
+                return Ok(SolverResult {
+                    success: reason.is_success(),
+                    cost: costs[best_idx],
+                    iterations: iter + 1,
+                    message: reason.message(),
+                    max_violation: problem.max_constraint_violation(&population[best_idx]),
+                    params: population[best_idx].clone(),
+                    cost_evals,
+                    grad_evals: 0,
+                });
+            }
+
+            if costs[best_idx] < self.precision {
+                return Ok(SolverResult {
+                    success: true,
+                    cost: costs[best_idx],
+                    iterations: iter + 1,
+                    message: "Converged".into(),
+                    max_violation: problem.max_constraint_violation(&population[best_idx]),
+                    params: population[best_idx].clone(),
+                    cost_evals,
+                    grad_evals: 0,
+                });
+            }
+
+            let mean_cost = costs.iter().sum::<f64>() / popsize as f64;
+            let variance = costs.iter().map(|c| (c - mean_cost).powi(2)).sum::<f64>() / popsize as f64;
+            if variance.sqrt() <= self.atol + self.tol * mean_cost.abs() {
+                return Ok(SolverResult {
+                    success: true,
+                    cost: costs[best_idx],
+                    iterations: iter + 1,
+                    message: "Converged (population cost spread collapsed)".into(),
+                    max_violation: problem.max_constraint_violation(&population[best_idx]),
+                    params: population[best_idx].clone(),
+                    cost_evals,
+                    grad_evals: 0,
+                });
+            }
+
+            let f = self.sample_f(&mut rng);
+
+            // Build every trial vector first, then cost the whole generation
+            // in one batch - same rationale as `CMAESOptimizer`: lets a
+            // problem with a worker pool fan trials out across simulators.
+            let mut trials = Vec::with_capacity(popsize);
+            for i in 0..popsize {
+                let needed = match self.strategy {
+                    Strategy::Best1Bin => 2,
+                    Strategy::Rand1Bin => 3,
+                };
+                let picked = self.distinct_indices(popsize, i, &mut rng, needed);
+                let (donor, r1, r2) = match self.strategy {
+                    Strategy::Best1Bin => (&population[best_idx], picked[0], picked[1]),
+                    Strategy::Rand1Bin => (&population[picked[0]], picked[1], picked[2]),
+                };
+
+                let mutant: Vec<f64> = (0..n)
+                    .map(|j| donor[j] + f * (population[r1][j] - population[r2][j]))
+                    .collect();
+
+                let j_rand = rng.gen_range(0..n);
+                let mut trial = population[i].clone();
+                for j in 0..n {
+                    if j == j_rand || rng.gen::<f64>() < self.recombination {
+                        trial[j] = mutant[j];
+                    }
+                }
+
+                self.clamp_params(&mut trial, bounds);
+                problem.apply_constraints(&mut trial)?;
+                trials.push(trial);
+            }
+
+            let mut trial_costs = match &thread_pool {
+                Some(pool) => pool.install(|| problem.cost_batch(&trials))?,
+                None => problem.cost_batch(&trials)?,
+            };
+            constraint_penalty::apply_to_batch(self.constraint_penalty.as_ref(), &mut trial_costs, &trials);
+            cost_evals += trial_costs.len();
+
+            // Greedy selection: a trial replaces its target only if it's no
+            // worse.
+            for i in 0..popsize {
+                if trial_costs[i] <= costs[i] {
+                    population[i] = trials[i].clone();
+                    costs[i] = trial_costs[i];
+                }
+            }
+
+            best_idx = (0..popsize)
+                .min_by(|&a, &b| costs[a].partial_cmp(&costs[b]).unwrap())
+                .unwrap();
+        }
+
+        Ok(SolverResult {
+            success: false,
+            cost: costs[best_idx],
+            iterations: self.max_iter,
+            message: "Max iterations reached".into(),
+            max_violation: problem.max_constraint_violation(&population[best_idx]),
+            params: population[best_idx].clone(),
+            cost_evals,
+            grad_evals: 0,
+        })
+    }
+}
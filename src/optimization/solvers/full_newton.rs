@@ -0,0 +1,285 @@
+use super::traits::{OptimizationCallback, Problem, Solver, SolverResult, StopReason};
+use std::ops::ControlFlow;
+
+/// True second-order Newton optimizer: builds the Hessian by finite
+/// differences and solves `(H + lambda*I) dx = -grad` for the step,
+/// Levenberg-Marquardt damping `lambda` up whenever the factorization finds
+/// the damped Hessian isn't positive definite. Complements the
+/// gradient-descent-with-line-search `NewtonOptimizer` with real quadratic
+/// convergence near a smooth optimum, at the cost of `O(n^2)` cost calls
+/// per Hessian.
+pub struct FullNewtonOptimizer {
+    max_iter: u32,
+    precision: f64,
+    initial_lambda: f64,
+    lambda_increase: f64,
+    max_lambda: f64,
+    fallback_step: f64,
+}
+
+impl FullNewtonOptimizer {
+    pub fn new(max_iter: u32, precision: f64) -> Self {
+        Self {
+            max_iter,
+            precision,
+            initial_lambda: 1e-3,
+            lambda_increase: 10.0,
+            max_lambda: 1e8,
+            fallback_step: 1e-3,
+        }
+    }
+
+    #[inline]
+    fn clamp_params(&self, params: &mut [f64], bounds: &[(f64, f64)]) {
+        for (i, &(min, max)) in bounds.iter().enumerate() {
+            params[i] = params[i].clamp(min, max);
+        }
+    }
+
+    /// Central-difference gradient (2n cost calls).
+    fn gradient(
+        &self,
+        problem: &dyn Problem,
+        x: &[f64],
+        h: f64,
+        cost_evals: &mut usize,
+    ) -> Result<Vec<f64>, String> {
+        let n = x.len();
+        let mut grad = vec![0.0; n];
+        for i in 0..n {
+            let mut plus = x.to_vec();
+            let mut minus = x.to_vec();
+            plus[i] += h;
+            minus[i] -= h;
+            let c_plus = problem.cost(&plus)?;
+            let c_minus = problem.cost(&minus)?;
+            *cost_evals += 2;
+            grad[i] = (c_plus - c_minus) / (2.0 * h);
+        }
+        Ok(grad)
+    }
+
+    /// Symmetric Hessian via the standard central second-difference
+    /// stencil: the diagonal reuses `f(x)` plus two on-axis probes per
+    /// parameter, and each off-diagonal entry takes four probes of its own.
+    fn hessian(
+        &self,
+        problem: &dyn Problem,
+        x: &[f64],
+        f0: f64,
+        h: f64,
+        cost_evals: &mut usize,
+    ) -> Result<Vec<Vec<f64>>, String> {
+        let n = x.len();
+        let h2 = 4.0 * h * h;
+
+        let mut axis_plus = vec![0.0; n];
+        let mut axis_minus = vec![0.0; n];
+        for i in 0..n {
+            let mut plus = x.to_vec();
+            let mut minus = x.to_vec();
+            plus[i] += 2.0 * h;
+            minus[i] -= 2.0 * h;
+            axis_plus[i] = problem.cost(&plus)?;
+            axis_minus[i] = problem.cost(&minus)?;
+            *cost_evals += 2;
+        }
+
+        let mut hess = vec![vec![0.0; n]; n];
+        for i in 0..n {
+            hess[i][i] = (axis_plus[i] - 2.0 * f0 + axis_minus[i]) / h2;
+        }
+
+        for i in 0..n {
+            for j in (i + 1)..n {
+                let mut pp = x.to_vec();
+                let mut pm = x.to_vec();
+                let mut mp = x.to_vec();
+                let mut mm = x.to_vec();
+                pp[i] += h;
+                pp[j] += h;
+                pm[i] += h;
+                pm[j] -= h;
+                mp[i] -= h;
+                mp[j] += h;
+                mm[i] -= h;
+                mm[j] -= h;
+
+                let c_pp = problem.cost(&pp)?;
+                let c_pm = problem.cost(&pm)?;
+                let c_mp = problem.cost(&mp)?;
+                let c_mm = problem.cost(&mm)?;
+                *cost_evals += 4;
+
+                let h_ij = (c_pp - c_pm - c_mp + c_mm) / h2;
+                hess[i][j] = h_ij;
+                hess[j][i] = h_ij;
+            }
+        }
+
+        Ok(hess)
+    }
+
+    /// Attempt a Cholesky factorization of `H + lambda*I` and solve for
+    /// `dx` in `(H + lambda*I) dx = -grad`. Returns `None` if the damped
+    /// matrix isn't positive definite (a negative or zero pivot), signaling
+    /// the caller to raise `lambda` and retry.
+    fn damped_solve(hess: &[Vec<f64>], grad: &[f64], lambda: f64) -> Option<Vec<f64>> {
+        let n = grad.len();
+        let mut a = vec![vec![0.0; n]; n];
+        for i in 0..n {
+            for j in 0..n {
+                a[i][j] = hess[i][j] + if i == j { lambda } else { 0.0 };
+            }
+        }
+
+        // Cholesky decomposition: A = L L^T
+        let mut l = vec![vec![0.0; n]; n];
+        for i in 0..n {
+            for j in 0..=i {
+                let mut sum = a[i][j];
+                for k in 0..j {
+                    sum -= l[i][k] * l[j][k];
+                }
+                if i == j {
+                    if sum <= 1e-300 {
+                        return None;
+                    }
+                    l[i][j] = sum.sqrt();
+                } else {
+                    l[i][j] = sum / l[j][j];
+                }
+            }
+        }
+
+        // Solve L y = -grad
+        let mut y = vec![0.0; n];
+        for i in 0..n {
+            let mut sum = -grad[i];
+            for k in 0..i {
+                sum -= l[i][k] * y[k];
+            }
+            y[i] = sum / l[i][i];
+        }
+
+        // Solve L^T dx = y
+        let mut dx = vec![0.0; n];
+        for i in (0..n).rev() {
+            let mut sum = y[i];
+            for k in (i + 1)..n {
+                sum -= l[k][i] * dx[k];
+            }
+            dx[i] = sum / l[i][i];
+        }
+
+        Some(dx)
+    }
+}
+
+impl Solver for FullNewtonOptimizer {
+    fn name(&self) -> &str {
+        "FullNewton"
+    }
+
+    fn solve(
+        &mut self,
+        problem: &dyn Problem,
+        callback: &mut dyn OptimizationCallback,
+    ) -> Result<SolverResult, String> {
+        let n = problem.num_params();
+        let bounds = problem.bounds();
+        let h = 1e-4;
+
+        let mut params = problem.initial_params().to_vec();
+        problem.apply_constraints(&mut params)?;
+        self.clamp_params(&mut params, bounds);
+
+        let mut cost_evals = 0usize;
+        let mut grad_evals = 0usize;
+        let mut lambda = self.initial_lambda;
+
+        for iter in 0..self.max_iter {
+            let cost = problem.cost(&params)?;
+            cost_evals += 1;
+
+            if let ControlFlow::Break(reason) = callback.on_iteration(iter + 1, &params, cost) {
+                if let StopReason::SimulationError(e) = reason {
+                    return Err(e);
+                }
+                return Ok(SolverResult {
+                    success: reason.is_success(),
+                    cost,
+                    iterations: iter + 1,
+                    message: reason.message(),
+                    max_violation: problem.max_constraint_violation(&params),
+                    params,
+                    cost_evals,
+                    grad_evals,
+                });
+            }
+
+            if cost < self.precision {
+                return Ok(SolverResult {
+                    success: true,
+                    cost,
+                    iterations: iter + 1,
+                    message: "Converged".into(),
+                    max_violation: problem.max_constraint_violation(&params),
+                    params,
+                    cost_evals,
+                    grad_evals,
+                });
+            }
+
+            let grad = self.gradient(problem, &params, h, &mut cost_evals)?;
+            let hess = self.hessian(problem, &params, cost, h, &mut cost_evals)?;
+            grad_evals += 1;
+
+            let mut dx = None;
+            while lambda <= self.max_lambda {
+                if let Some(step) = Self::damped_solve(&hess, &grad, lambda) {
+                    dx = Some(step);
+                    break;
+                }
+                lambda *= self.lambda_increase;
+            }
+
+            match dx {
+                Some(step) => {
+                    for i in 0..n {
+                        params[i] += step[i];
+                    }
+                    // Damping succeeded - relax it back down for the next
+                    // iteration so we don't stay stuck near gradient descent.
+                    lambda = (lambda / self.lambda_increase).max(self.initial_lambda);
+                }
+                None => {
+                    // Hessian never became positive definite even at
+                    // max_lambda - fall back to a small gradient step.
+                    for i in 0..n {
+                        params[i] -= self.fallback_step * grad[i];
+                    }
+                    lambda = self.initial_lambda;
+                }
+            }
+
+            self.clamp_params(&mut params, bounds);
+            problem.apply_constraints(&mut params)?;
+            self.clamp_params(&mut params, bounds);
+        }
+
+        let cost = problem.cost(&params)?;
+        cost_evals += 1;
+
+        Ok(SolverResult {
+            success: false,
+            cost,
+            iterations: self.max_iter,
+            message: "Max iterations reached".into(),
+            max_violation: problem.max_constraint_violation(&params),
+            params,
+            cost_evals,
+            grad_evals,
+        })
+    }
+}
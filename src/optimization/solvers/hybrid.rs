@@ -0,0 +1,339 @@
+use super::traits::{OptimizationCallback, Problem, Solver, SolverResult, StopReason};
+use rand::Rng;
+use std::ops::ControlFlow;
+
+/// Memetic PSO + local-Newton hybrid: global search locates the right
+/// basin, periodic local refinement polishes it down to the basin's exact
+/// minimum. Both halves are written out here rather than delegated: the
+/// swarm update repeats [`super::ParticleOptimizer`]'s recurrence, and the
+/// refinement is a bounded gradient-descent-with-Armijo-backtracking loop on
+/// the same gradient stencil as [`super::NewtonOptimizer`], so the refined
+/// point can be written back into the swarm's personal-best memory between
+/// PSO steps.
+pub struct HybridOptimizer {
+    max_iter: u32,
+    precision: f64,
+    population_size: usize,
+    inertia: f64,
+    cognitive: f64,
+    social: f64,
+    /// Fraction of `max_iter` that runs as pure PSO before local refinement
+    /// starts firing.
+    pso_fraction: f64,
+    /// PSO iterations between local refinement bursts, once the pure-PSO
+    /// phase above has elapsed.
+    refine_every: u32,
+    /// Bounded number of gradient-descent-with-line-search steps each
+    /// refinement burst takes on the current global best.
+    local_steps: u32,
+}
+
+impl HybridOptimizer {
+    pub fn new(max_iter: u32, precision: f64) -> Self {
+        Self {
+            max_iter,
+            precision,
+            population_size: 20,
+            inertia: 0.7,
+            cognitive: 1.5,
+            social: 1.5,
+            pso_fraction: 0.6,
+            refine_every: 5,
+            local_steps: 5,
+        }
+    }
+
+    /// Configure swarm size (default: 20)
+    pub fn with_population_size(mut self, size: usize) -> Self {
+        self.population_size = size;
+        self
+    }
+
+    /// Configure PSO parameters (defaults: w=0.7, c1=1.5, c2=1.5)
+    pub fn with_pso_params(mut self, inertia: f64, cognitive: f64, social: f64) -> Self {
+        self.inertia = inertia;
+        self.cognitive = cognitive;
+        self.social = social;
+        self
+    }
+
+    /// Configure the PSO/local split: `fraction` of `max_iter` runs as pure
+    /// PSO before local refinement starts interleaving (default: 0.6).
+    pub fn with_split_ratio(mut self, fraction: f64) -> Self {
+        self.pso_fraction = fraction.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Configure how often (in PSO iterations) local refinement fires once
+    /// the pure-PSO phase has elapsed (default: every 5 iterations).
+    pub fn with_refine_every(mut self, n: u32) -> Self {
+        self.refine_every = n.max(1);
+        self
+    }
+
+    /// Configure how many bounded local steps each refinement burst takes
+    /// (default: 5).
+    pub fn with_local_steps(mut self, n: u32) -> Self {
+        self.local_steps = n.max(1);
+        self
+    }
+
+    #[inline]
+    fn clamp_params(&self, params: &mut [f64], bounds: &[(f64, f64)]) {
+        for (i, &(min, max)) in bounds.iter().enumerate() {
+            params[i] = params[i].clamp(min, max);
+        }
+    }
+
+    fn initialize_particles(
+        &self,
+        n_params: usize,
+        bounds: &[(f64, f64)],
+        initial_params: &[f64],
+    ) -> Vec<Vec<f64>> {
+        let mut rng = rand::thread_rng();
+        let mut particles = Vec::with_capacity(self.population_size);
+        particles.push(initial_params.to_vec());
+        for _ in 1..self.population_size {
+            let mut particle = vec![0.0; n_params];
+            for i in 0..n_params {
+                let (min, max) = bounds[i];
+                particle[i] = rng.gen_range(min..=max);
+            }
+            particles.push(particle);
+        }
+        particles
+    }
+
+    fn initialize_velocities(&self, n_params: usize, bounds: &[(f64, f64)]) -> Vec<Vec<f64>> {
+        let mut rng = rand::thread_rng();
+        let mut velocities = Vec::with_capacity(self.population_size);
+        for _ in 0..self.population_size {
+            let mut velocity = vec![0.0; n_params];
+            for i in 0..n_params {
+                let (min, max) = bounds[i];
+                let range = max - min;
+                velocity[i] = rng.gen_range(-range * 0.1..=range * 0.1);
+            }
+            velocities.push(velocity);
+        }
+        velocities
+    }
+
+    /// Central finite-difference gradient, same stencil [`super::NewtonOptimizer`] uses.
+    fn gradient(
+        &self,
+        problem: &dyn Problem,
+        x: &[f64],
+        cost_evals: &mut usize,
+    ) -> Result<Vec<f64>, String> {
+        let h = 1e-6;
+        let mut grad = vec![0.0; x.len()];
+        for i in 0..x.len() {
+            let mut plus = x.to_vec();
+            let mut minus = x.to_vec();
+            plus[i] += h;
+            minus[i] -= h;
+            let c_plus = problem.cost(&plus)?;
+            let c_minus = problem.cost(&minus)?;
+            *cost_evals += 2;
+            grad[i] = (c_plus - c_minus) / (2.0 * h);
+        }
+        Ok(grad)
+    }
+
+    /// A handful of gradient-descent-with-Armijo-backtracking steps, bounded
+    /// to `self.local_steps`, starting from `(x0, cost0)`. Returns the
+    /// refined point and its cost, never worse than the starting point.
+    fn local_refine(
+        &self,
+        problem: &dyn Problem,
+        bounds: &[(f64, f64)],
+        x0: &[f64],
+        cost0: f64,
+        cost_evals: &mut usize,
+        grad_evals: &mut usize,
+    ) -> Result<(Vec<f64>, f64), String> {
+        let mut x = x0.to_vec();
+        let mut cost = cost0;
+        let mut step = 0.1;
+
+        for _ in 0..self.local_steps {
+            let grad = self.gradient(problem, &x, cost_evals)?;
+            *grad_evals += 1;
+            let grad_norm_sq: f64 = grad.iter().map(|g| g * g).sum();
+            if grad_norm_sq < 1e-300 {
+                break;
+            }
+
+            let mut improved = false;
+            let mut alpha = step;
+            for _ in 0..10 {
+                let mut trial = x.clone();
+                for i in 0..trial.len() {
+                    trial[i] -= alpha * grad[i];
+                }
+                self.clamp_params(&mut trial, bounds);
+
+                let trial_cost = problem.cost(&trial)?;
+                *cost_evals += 1;
+
+                if trial_cost <= cost - 1e-4 * alpha * grad_norm_sq {
+                    x = trial;
+                    cost = trial_cost;
+                    step = alpha;
+                    improved = true;
+                    break;
+                }
+                alpha *= 0.5;
+            }
+
+            if !improved {
+                break;
+            }
+        }
+
+        Ok((x, cost))
+    }
+}
+
+impl Solver for HybridOptimizer {
+    fn name(&self) -> &str {
+        "Hybrid"
+    }
+
+    fn solve(
+        &mut self,
+        problem: &dyn Problem,
+        callback: &mut dyn OptimizationCallback,
+    ) -> Result<SolverResult, String> {
+        let n = problem.num_params();
+        let bounds = problem.bounds();
+        let mut rng = rand::thread_rng();
+
+        let mut particles = self.initialize_particles(n, bounds, problem.initial_params());
+        let mut velocities = self.initialize_velocities(n, bounds);
+        let mut personal_best_positions = particles.clone();
+        let mut personal_best_costs = vec![f64::INFINITY; self.population_size];
+
+        let mut global_best_idx = 0;
+        let mut global_best_cost = f64::INFINITY;
+
+        let mut cost_evals = 0usize;
+        let mut grad_evals = 0usize;
+
+        let pso_only_iters = (self.max_iter as f64 * self.pso_fraction).round() as u32;
+
+        for iter in 0..self.max_iter {
+            for p in 0..self.population_size {
+                problem.apply_constraints(&mut particles[p])?;
+                self.clamp_params(&mut particles[p], bounds);
+            }
+
+            let costs = problem.cost_batch(&particles)?;
+            cost_evals += costs.len();
+
+            for p in 0..self.population_size {
+                let cost = costs[p];
+                if cost < personal_best_costs[p] {
+                    personal_best_costs[p] = cost;
+                    personal_best_positions[p].copy_from_slice(&particles[p]);
+                }
+                if cost < global_best_cost {
+                    global_best_cost = cost;
+                    global_best_idx = p;
+                }
+            }
+
+            // Periodic local refinement: once the pure-PSO phase has
+            // elapsed, every `refine_every` iterations hand the current
+            // global best to a bounded gradient-descent burst and write the
+            // improved point back into the swarm's personal-best memory so
+            // PSO keeps exploring around the refined optimum.
+            if iter >= pso_only_iters && (iter - pso_only_iters) % self.refine_every == 0 {
+                let (refined_params, refined_cost) = self.local_refine(
+                    problem,
+                    bounds,
+                    &personal_best_positions[global_best_idx],
+                    global_best_cost,
+                    &mut cost_evals,
+                    &mut grad_evals,
+                )?;
+
+                if refined_cost < global_best_cost {
+                    personal_best_positions[global_best_idx] = refined_params.clone();
+                    personal_best_costs[global_best_idx] = refined_cost;
+                    particles[global_best_idx] = refined_params;
+                    global_best_cost = refined_cost;
+                }
+            }
+
+            if let ControlFlow::Break(reason) = callback.on_iteration(
+                iter + 1,
+                &personal_best_positions[global_best_idx],
+                global_best_cost,
+            ) {
+                if let StopReason::SimulationError(e) = reason {
+                    return Err(e);
+                }
+                return Ok(SolverResult {
+                    success: reason.is_success(),
+                    cost: global_best_cost,
+                    iterations: iter + 1,
+                    message: reason.message(),
+                    max_violation: problem
+                        .max_constraint_violation(&personal_best_positions[global_best_idx]),
+                    params: personal_best_positions[global_best_idx].clone(),
+                    cost_evals,
+                    grad_evals,
+                });
+            }
+
+            if global_best_cost < self.precision {
+                return Ok(SolverResult {
+                    success: true,
+                    cost: global_best_cost,
+                    iterations: iter + 1,
+                    message: "Converged".into(),
+                    max_violation: problem
+                        .max_constraint_violation(&personal_best_positions[global_best_idx]),
+                    params: personal_best_positions[global_best_idx].clone(),
+                    cost_evals,
+                    grad_evals,
+                });
+            }
+
+            for p in 0..self.population_size {
+                for i in 0..n {
+                    let r1 = rng.gen::<f64>();
+                    let r2 = rng.gen::<f64>();
+
+                    velocities[p][i] = self.inertia * velocities[p][i]
+                        + self.cognitive * r1 * (personal_best_positions[p][i] - particles[p][i])
+                        + self.social
+                            * r2
+                            * (personal_best_positions[global_best_idx][i] - particles[p][i]);
+
+                    let (min, max) = bounds[i];
+                    let v_max = (max - min) * 0.2;
+                    velocities[p][i] = velocities[p][i].clamp(-v_max, v_max);
+
+                    particles[p][i] += velocities[p][i];
+                }
+                self.clamp_params(&mut particles[p], bounds);
+            }
+        }
+
+        Ok(SolverResult {
+            success: false,
+            cost: global_best_cost,
+            iterations: self.max_iter,
+            message: "Max iterations reached".into(),
+            max_violation: problem
+                .max_constraint_violation(&personal_best_positions[global_best_idx]),
+            params: personal_best_positions[global_best_idx].clone(),
+            cost_evals,
+            grad_evals,
+        })
+    }
+}
@@ -0,0 +1,361 @@
+use super::traits::{OptimizationCallback, Problem, Solver, SolverResult, StopReason};
+use std::collections::VecDeque;
+use std::ops::ControlFlow;
+
+/// Limited-memory BFGS with box constraints (L-BFGS-B).
+///
+/// Keeps the last `m` correction pairs `(s_k, y_k)` and on each iteration:
+/// 1. walks the projected-gradient path to find the generalized Cauchy point,
+///    which fixes the active (bound-hitting) set;
+/// 2. minimizes the quadratic model over the free variables using the
+///    two-loop recursion to apply the implicit inverse-Hessian;
+/// 3. performs a projected backtracking line search on the Armijo
+///    sufficient-decrease condition; the strong Wolfe curvature condition is
+///    not enforced (see `line_search`).
+pub struct LBFGSBOptimizer {
+    max_iter: u32,
+    precision: f64,
+    history_size: usize,
+    c1: f64, // Wolfe sufficient decrease
+    c2: f64, // Wolfe curvature
+}
+
+impl LBFGSBOptimizer {
+    pub fn new(max_iter: u32, precision: f64) -> Self {
+        Self {
+            max_iter,
+            precision,
+            history_size: 8,
+            c1: 1e-4,
+            c2: 0.9,
+        }
+    }
+
+    pub fn with_history_size(mut self, m: usize) -> Self {
+        self.history_size = m.max(1);
+        self
+    }
+
+    #[inline]
+    fn project(x: &[f64], bounds: &[(f64, f64)]) -> Vec<f64> {
+        x.iter()
+            .zip(bounds.iter())
+            .map(|(&v, &(lo, hi))| v.clamp(lo, hi))
+            .collect()
+    }
+
+    /// Central finite-difference gradient.
+    fn gradient(
+        &self,
+        problem: &dyn Problem,
+        x: &[f64],
+        cost_evals: &mut usize,
+    ) -> Result<Vec<f64>, String> {
+        let h = 1e-6;
+        let mut grad = vec![0.0; x.len()];
+        for i in 0..x.len() {
+            let mut plus = x.to_vec();
+            let mut minus = x.to_vec();
+            plus[i] += h;
+            minus[i] -= h;
+            let c_plus = problem.cost(&plus)?;
+            let c_minus = problem.cost(&minus)?;
+            *cost_evals += 2;
+            grad[i] = (c_plus - c_minus) / (2.0 * h);
+        }
+        Ok(grad)
+    }
+
+    /// Generalized Cauchy point: walk the projected-gradient path
+    /// `x(t) = P(x - t*g, l, u)` and stop at the first local minimizer of the
+    /// unit-curvature quadratic model `g.z + |z|^2 / 2` (with `z = x(t) - x`)
+    /// along the path. Returns the Cauchy point and the set
+    /// of variable indices that are "free" (not fixed at a bound).
+    fn cauchy_point(x: &[f64], g: &[f64], bounds: &[(f64, f64)]) -> (Vec<f64>, Vec<bool>) {
+        let n = x.len();
+        // Breakpoints: time at which each coordinate hits its bound.
+        let mut t_bp = vec![f64::INFINITY; n];
+        for i in 0..n {
+            let (lo, hi) = bounds[i];
+            if g[i] < 0.0 && hi.is_finite() {
+                t_bp[i] = (hi - x[i]) / (-g[i]);
+            } else if g[i] > 0.0 && lo.is_finite() {
+                t_bp[i] = (x[i] - lo) / g[i];
+            }
+        }
+
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&a, &b| t_bp[a].partial_cmp(&t_bp[b]).unwrap());
+
+        let mut xc = x.to_vec();
+        let mut free = vec![true; n];
+        let mut t_prev = 0.0;
+        // Direction along the path before any breakpoint is -g.
+        let mut d: Vec<f64> = g.iter().map(|&gi| -gi).collect();
+
+        for &i in &order {
+            let t_i = t_bp[i];
+            if !t_i.is_finite() {
+                break;
+            }
+            let dt = t_i - t_prev;
+            // Unit-curvature quadratic model along this segment: stop at its
+            // minimizer when that comes before the next breakpoint.
+            let f1: f64 = (0..n).map(|j| (g[j] + xc[j] - x[j]) * d[j]).sum();
+            let f2: f64 = d.iter().map(|dj| dj * dj).sum();
+            if f1 >= 0.0 || (f2 > 0.0 && -f1 / f2 < dt) {
+                let t_min = if f1 >= 0.0 { 0.0 } else { -f1 / f2 };
+                for j in 0..n {
+                    xc[j] += t_min * d[j];
+                }
+                break;
+            }
+            if dt > 0.0 {
+                for &j in &order {
+                    if free[j] {
+                        xc[j] += dt * d[j];
+                    }
+                }
+            }
+            // Fix coordinate i at its bound.
+            let (lo, hi) = bounds[i];
+            xc[i] = if g[i] < 0.0 { hi } else { lo };
+            free[i] = false;
+            d[i] = 0.0;
+            t_prev = t_i;
+        }
+
+        (xc, free)
+    }
+
+    /// Two-loop recursion applying the implicit inverse-Hessian to `-grad`,
+    /// restricted to the free variables.
+    fn two_loop_direction(
+        grad: &[f64],
+        free: &[bool],
+        s_hist: &VecDeque<Vec<f64>>,
+        y_hist: &VecDeque<Vec<f64>>,
+        rho: &VecDeque<f64>,
+    ) -> Vec<f64> {
+        let n = grad.len();
+        let mut q: Vec<f64> = grad.iter().map(|&g| -g).collect();
+        let k = s_hist.len();
+        let mut alpha = vec![0.0; k];
+
+        for i in (0..k).rev() {
+            let dot: f64 = (0..n).map(|j| s_hist[i][j] * q[j]).sum();
+            alpha[i] = rho[i] * dot;
+            for j in 0..n {
+                q[j] -= alpha[i] * y_hist[i][j];
+            }
+        }
+
+        // Initial Hessian scaling using the most recent pair.
+        let gamma = if let (Some(s), Some(y)) = (s_hist.back(), y_hist.back()) {
+            let sy: f64 = (0..n).map(|j| s[j] * y[j]).sum();
+            let yy: f64 = (0..n).map(|j| y[j] * y[j]).sum();
+            if yy > 0.0 {
+                sy / yy
+            } else {
+                1.0
+            }
+        } else {
+            1.0
+        };
+        for v in q.iter_mut() {
+            *v *= gamma;
+        }
+
+        for i in 0..k {
+            let dot: f64 = (0..n).map(|j| y_hist[i][j] * q[j]).sum();
+            let beta = rho[i] * dot;
+            for j in 0..n {
+                q[j] += s_hist[i][j] * (alpha[i] - beta);
+            }
+        }
+
+        // Fixed variables don't move.
+        for j in 0..n {
+            if !free[j] {
+                q[j] = 0.0;
+            }
+        }
+        q
+    }
+
+    /// Projected backtracking line search that returns the first step meeting
+    /// the Armijo sufficient-decrease condition. The curvature probe taken
+    /// after it never rejects that step along a descent direction, since
+    /// Armijo then already implies `f1 < f0`. If no step passes within 20
+    /// halvings, the smallest step is returned unchecked.
+    fn line_search(
+        &self,
+        problem: &dyn Problem,
+        x: &[f64],
+        f0: f64,
+        g0: &[f64],
+        direction: &[f64],
+        bounds: &[(f64, f64)],
+        cost_evals: &mut usize,
+    ) -> Result<(Vec<f64>, f64), String> {
+        let dir_slope: f64 = g0.iter().zip(direction.iter()).map(|(g, d)| g * d).sum();
+        let mut alpha = 1.0;
+
+        for _ in 0..20 {
+            let trial: Vec<f64> = x
+                .iter()
+                .zip(direction.iter())
+                .map(|(&xi, &di)| xi + alpha * di)
+                .collect();
+            let projected = Self::project(&trial, bounds);
+            let f1 = problem.cost(&projected)?;
+            *cost_evals += 1;
+
+            let armijo = f1 <= f0 + self.c1 * alpha * dir_slope;
+            if armijo {
+                // Approximate curvature check using a forward-difference
+                // directional derivative rather than another full gradient.
+                let h = 1e-6;
+                let bumped: Vec<f64> = projected
+                    .iter()
+                    .zip(direction.iter())
+                    .map(|(&xi, &di)| xi + h * di)
+                    .collect();
+                let f_bump = problem.cost(&Self::project(&bumped, bounds))?;
+                *cost_evals += 1;
+                let slope_at_new = (f_bump - f1) / h;
+                if slope_at_new.abs() <= self.c2 * dir_slope.abs() || f1 < f0 {
+                    return Ok((projected, f1));
+                }
+            }
+            alpha *= 0.5;
+        }
+
+        let trial: Vec<f64> = x
+            .iter()
+            .zip(direction.iter())
+            .map(|(&xi, &di)| xi + alpha * di)
+            .collect();
+        let projected = Self::project(&trial, bounds);
+        let f1 = problem.cost(&projected)?;
+        *cost_evals += 1;
+        Ok((projected, f1))
+    }
+}
+
+impl Solver for LBFGSBOptimizer {
+    fn name(&self) -> &str {
+        "L-BFGS-B"
+    }
+
+    fn solve(
+        &mut self,
+        problem: &dyn Problem,
+        callback: &mut dyn OptimizationCallback,
+    ) -> Result<SolverResult, String> {
+        let bounds = problem.bounds().to_vec();
+        let mut x = Self::project(problem.initial_params(), &bounds);
+        problem.apply_constraints(&mut x)?;
+
+        let mut cost_evals = 0usize;
+        let mut grad_evals = 0usize;
+
+        let mut s_hist: VecDeque<Vec<f64>> = VecDeque::with_capacity(self.history_size);
+        let mut y_hist: VecDeque<Vec<f64>> = VecDeque::with_capacity(self.history_size);
+        let mut rho: VecDeque<f64> = VecDeque::with_capacity(self.history_size);
+
+        let mut cost = problem.cost(&x)?;
+        cost_evals += 1;
+        let mut grad = self.gradient(problem, &x, &mut cost_evals)?;
+        grad_evals += 1;
+
+        for iter in 0..self.max_iter {
+            if let ControlFlow::Break(reason) = callback.on_iteration(iter + 1, &x, cost) {
+                if let StopReason::SimulationError(e) = reason {
+                    return Err(e);
+                }
+                return Ok(SolverResult {
+                    success: reason.is_success(),
+                    cost,
+                    iterations: iter + 1,
+                    message: reason.message(),
+                    max_violation: problem.max_constraint_violation(&x),
+                    params: x,
+                    cost_evals,
+                    grad_evals,
+                });
+            }
+
+            if cost < self.precision {
+                return Ok(SolverResult {
+                    success: true,
+                    cost,
+                    iterations: iter + 1,
+                    message: "Converged".into(),
+                    max_violation: problem.max_constraint_violation(&x),
+                    params: x,
+                    cost_evals,
+                    grad_evals,
+                });
+            }
+
+            let (_cauchy, free) = Self::cauchy_point(&x, &grad, &bounds);
+            let direction = Self::two_loop_direction(&grad, &free, &s_hist, &y_hist, &rho);
+
+            let dir_norm: f64 = direction.iter().map(|d| d * d).sum::<f64>().sqrt();
+            if dir_norm < 1e-12 {
+                return Ok(SolverResult {
+                    success: true,
+                    cost,
+                    iterations: iter + 1,
+                    message: "Converged (no free descent direction)".into(),
+                    max_violation: problem.max_constraint_violation(&x),
+                    params: x,
+                    cost_evals,
+                    grad_evals,
+                });
+            }
+
+            let (x_new, cost_new) =
+                self.line_search(problem, &x, cost, &grad, &direction, &bounds, &mut cost_evals)?;
+
+            let mut x_new = x_new;
+            problem.apply_constraints(&mut x_new)?;
+
+            let grad_new = self.gradient(problem, &x_new, &mut cost_evals)?;
+            grad_evals += 1;
+
+            let s: Vec<f64> = x_new.iter().zip(x.iter()).map(|(n, o)| n - o).collect();
+            let y: Vec<f64> = grad_new.iter().zip(grad.iter()).map(|(n, o)| n - o).collect();
+            let sy: f64 = s.iter().zip(y.iter()).map(|(si, yi)| si * yi).sum();
+
+            // Curvature invariant: only accept the pair when y.s is
+            // sufficiently positive, otherwise skip the update entirely.
+            if sy > 1e-10 {
+                if s_hist.len() == self.history_size {
+                    s_hist.pop_front();
+                    y_hist.pop_front();
+                    rho.pop_front();
+                }
+                s_hist.push_back(s);
+                y_hist.push_back(y);
+                rho.push_back(1.0 / sy);
+            }
+
+            x = x_new;
+            grad = grad_new;
+            cost = cost_new;
+        }
+
+        Ok(SolverResult {
+            success: false,
+            cost,
+            iterations: self.max_iter,
+            message: "Max iterations reached".into(),
+            max_violation: problem.max_constraint_violation(&x),
+            params: x,
+            cost_evals,
+            grad_evals,
+        })
+    }
+}
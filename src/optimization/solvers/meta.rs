@@ -0,0 +1,180 @@
+use super::particle::ParticleOptimizer;
+use super::simulated_annealing::SimulatedAnnealing;
+use super::traits::{OptimizationCallback, Problem, Solver, SolverResult, StopReason};
+use std::ops::ControlFlow;
+
+/// Which solver's hyperparameters [`MetaOptimizer`] tunes. PSO is the only
+/// kind wired up today (its inertia/cognitive/social weights are exactly
+/// the "strongly affects convergence, users currently guess them" case);
+/// extending this to e.g. `NewtonOptimizer`'s learning-rate bounds just
+/// needs another variant and another `cost()` arm in [`HyperparamProblem`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BaseSolverKind {
+    Pso,
+}
+
+/// Which derivative-free solver drives the outer hyperparameter search.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OuterSearch {
+    Pso,
+    SimulatedAnnealing,
+}
+
+/// Tuned PSO hyperparameters, as found by [`MetaOptimizer::tune`].
+#[derive(Clone, Copy, Debug)]
+pub struct PsoHyperparams {
+    pub inertia: f64,
+    pub cognitive: f64,
+    pub social: f64,
+}
+
+/// No-op callback for the inner/outer solves `MetaOptimizer` drives
+/// internally - neither exposes progress to the caller, only the final
+/// tuned result does.
+struct NoopCallback;
+
+impl OptimizationCallback for NoopCallback {
+    fn on_iteration(
+        &mut self,
+        _iteration: u32,
+        _params: &[f64],
+        _cost: f64,
+    ) -> ControlFlow<StopReason, ()> {
+        ControlFlow::Continue(())
+    }
+}
+
+/// Wraps a target [`Problem`] as a 3-parameter (inertia, cognitive, social)
+/// hyperparameter-search problem: `cost()` runs a fresh `ParticleOptimizer`
+/// configured with the candidate hyperparameters against the target and
+/// returns its achieved cost, optionally penalized by how many simulations
+/// it took so cheaper configurations are favored among near-equal results.
+struct HyperparamProblem<'a> {
+    target: &'a dyn Problem,
+    inner_max_iter: u32,
+    inner_precision: f64,
+    cost_evals_penalty: f64,
+    initial: Vec<f64>,
+    bounds: Vec<(f64, f64)>,
+}
+
+impl<'a> Problem for HyperparamProblem<'a> {
+    fn cost(&self, params: &[f64]) -> Result<f64, String> {
+        let mut solver = ParticleOptimizer::new(self.inner_max_iter, self.inner_precision)
+            .with_pso_params(params[0], params[1], params[2]);
+        let mut cb = NoopCallback;
+        let result = solver.solve(self.target, &mut cb)?;
+        Ok(result.cost + self.cost_evals_penalty * result.cost_evals as f64)
+    }
+
+    fn num_params(&self) -> usize {
+        3
+    }
+
+    fn initial_params(&self) -> &[f64] {
+        &self.initial
+    }
+
+    fn bounds(&self) -> &[(f64, f64)] {
+        &self.bounds
+    }
+
+    fn apply_constraints(&self, params: &mut [f64]) -> Result<(), String> {
+        for (p, &(lo, hi)) in params.iter_mut().zip(self.bounds.iter()) {
+            *p = p.clamp(lo, hi);
+        }
+        Ok(())
+    }
+}
+
+/// Self-tuning meta-optimizer: treats a base solver's hyperparameters as an
+/// outer optimization problem, so a caller gets a solver auto-configured
+/// for a new circuit in one call instead of guessing `inertia`/`c1`/`c2` by
+/// hand. Wraps the existing [`Solver`] implementations without changing
+/// them - the outer search just drives fresh instances of the base solver
+/// as its cost function.
+pub struct MetaOptimizer {
+    kind: BaseSolverKind,
+    inner_max_iter: u32,
+    inner_precision: f64,
+    outer_search: OuterSearch,
+    outer_max_iter: u32,
+    outer_precision: f64,
+    cost_evals_penalty: f64,
+}
+
+impl MetaOptimizer {
+    /// `inner_max_iter`/`inner_precision` are the budget each candidate
+    /// hyperparameter vector's base-solver run gets.
+    pub fn new(kind: BaseSolverKind, inner_max_iter: u32, inner_precision: f64) -> Self {
+        Self {
+            kind,
+            inner_max_iter,
+            inner_precision,
+            outer_search: OuterSearch::Pso,
+            outer_max_iter: 20,
+            outer_precision: 0.0,
+            cost_evals_penalty: 0.0,
+        }
+    }
+
+    /// Configure the outer search strategy and its iteration budget
+    /// (default: PSO, 20 iterations).
+    pub fn with_outer_search(mut self, search: OuterSearch, max_iter: u32) -> Self {
+        self.outer_search = search;
+        self.outer_max_iter = max_iter;
+        self
+    }
+
+    /// Penalize the outer objective by `weight * cost_evals` so the search
+    /// favors cheap-to-run configurations among near-equal results
+    /// (default: 0.0, i.e. no penalty).
+    pub fn with_cost_evals_penalty(mut self, weight: f64) -> Self {
+        self.cost_evals_penalty = weight;
+        self
+    }
+
+    /// Tune the base solver's hyperparameters against `problem`, returning
+    /// them alongside a final [`SolverResult`] from re-running the
+    /// tuned solver.
+    pub fn tune(&self, problem: &dyn Problem) -> Result<(PsoHyperparams, SolverResult), String> {
+        match self.kind {
+            BaseSolverKind::Pso => self.tune_pso(problem),
+        }
+    }
+
+    fn tune_pso(&self, problem: &dyn Problem) -> Result<(PsoHyperparams, SolverResult), String> {
+        let hp_problem = HyperparamProblem {
+            target: problem,
+            inner_max_iter: self.inner_max_iter,
+            inner_precision: self.inner_precision,
+            cost_evals_penalty: self.cost_evals_penalty,
+            initial: vec![0.7, 1.5, 1.5],
+            bounds: vec![(0.0, 1.0), (0.0, 2.0), (0.0, 2.0)],
+        };
+
+        let mut outer_solver: Box<dyn Solver> = match self.outer_search {
+            OuterSearch::Pso => {
+                Box::new(ParticleOptimizer::new(self.outer_max_iter, self.outer_precision))
+            }
+            OuterSearch::SimulatedAnnealing => {
+                Box::new(SimulatedAnnealing::new(self.outer_max_iter, self.outer_precision))
+            }
+        };
+
+        let mut cb = NoopCallback;
+        let outer_result = outer_solver.solve(&hp_problem, &mut cb)?;
+
+        let tuned = PsoHyperparams {
+            inertia: outer_result.params[0],
+            cognitive: outer_result.params[1],
+            social: outer_result.params[2],
+        };
+
+        let mut final_solver = ParticleOptimizer::new(self.inner_max_iter, self.inner_precision)
+            .with_pso_params(tuned.inertia, tuned.cognitive, tuned.social);
+        let final_result = final_solver.solve(problem, &mut cb)?;
+
+        Ok((tuned, final_result))
+    }
+}
@@ -1,19 +1,48 @@
 mod cma_es;
+mod conjugate_gradient;
+mod constraint_penalty;
+mod differential_evolution;
+mod full_newton;
+mod hybrid;
+mod lbfgsb;
+mod meta;
 mod newton;
 mod particle;
+mod polish;
+mod portfolio;
+mod sampling;
+mod simulated_annealing;
 pub mod traits;
 
-pub use traits::{Problem, Solver, SolverResult};
+pub use traits::{BatchHandle, OptimizationCallback, Problem, Solver, SolverResult, StopReason};
 pub use cma_es::CMAESOptimizer;
+pub use conjugate_gradient::ConjugateGradientOptimizer;
+pub use constraint_penalty::{ConstraintPenalty, PenaltySchedule};
+pub use differential_evolution::{DifferentialEvolutionOptimizer, FRange, Strategy};
+pub use full_newton::FullNewtonOptimizer;
+pub use hybrid::HybridOptimizer;
+pub use lbfgsb::LBFGSBOptimizer;
+pub use meta::{BaseSolverKind, MetaOptimizer, OuterSearch, PsoHyperparams};
 pub use newton::NewtonOptimizer;
 pub use particle::ParticleOptimizer;
+pub use polish::PolishingSolver;
+pub use portfolio::PortfolioSolver;
+pub use sampling::InitMode;
+pub use simulated_annealing::{SimulatedAnnealing, TempSchedule};
 
+/// Pick a solver for the given problem shape, optionally wrapping it in a
+/// [`PolishingSolver`] when `polish` is set so the chosen global search gets
+/// a local-Newton refinement pass once it converges - see
+/// [`PolishingSolver`] for why this is a wrapper instead of a per-solver
+/// flag.
 pub fn select_solver(
     num_params: usize,
     bounds: &[(f64, f64)],
     has_constraints: bool,
     max_iterations: u32,
     precision: f64,
+    polish: bool,
+    constraint_penalty: Option<ConstraintPenalty>,
 ) -> (Box<dyn Solver>, String) {
     // Analyze parameter ranges
     let mut ranges = Vec::new();
@@ -41,6 +70,16 @@ pub fn select_solver(
     
     // Decision logic - prefer gradient-free methods for circuit optimization (noisy, non-convex)
     let (solver, reason): (Box<dyn Solver>, String) = match (num_params, has_tight_bounds, parameter_scale_variance, has_constraints) {
+        // Tiny problems with very tight bounds and no hard constraints ->
+        // L-BFGS-B, which respects the box directly instead of clamping
+        // after an unconstrained Newton step
+        (n, true, _, false) if n <= 2 && avg_range < 0.1 => {
+            (
+                Box::new(LBFGSBOptimizer::new(max_iterations, precision)),
+                format!("Auto: Tiny problem ({} params, range {:.3}) → L-BFGS-B (bound-aware gradient)", n, avg_range)
+            )
+        },
+
         // Small problems (1-2 params) with very tight bounds -> Newton as last resort
         (n, true, _, _) if n <= 2 && avg_range < 0.1 => {
             (
@@ -49,11 +88,29 @@ pub fn select_solver(
             )
         },
 
+        // Small to medium problems with poorly-scaled parameters -> DE
+        // (Storn-Price differences between population vectors aren't
+        // distorted by mismatched units the way PSO's per-dimension
+        // velocity is, and DE tolerates multimodal, noisy costs well)
+        (n, _, var, _) if n <= 8 && var > 1.5 => {
+            let mut de = DifferentialEvolutionOptimizer::new(max_iterations, precision);
+            if let Some(cp) = constraint_penalty {
+                de = de.with_constraint_penalty(cp);
+            }
+            (
+                Box::new(de),
+                format!("Auto: {} params, poorly scaled (var: {:.2}) → DE (robust to mismatched parameter units)", n, var)
+            )
+        },
+
         // Small to medium problems (1-8 params) -> PSO (best for circuit optimization)
         (n, _, _, _) if n <= 8 => {
             let pop_size = (10 + n * 3).min(30);  // Scale population: 10-30 particles
-            let pso = ParticleOptimizer::new(max_iterations, precision)
+            let mut pso = ParticleOptimizer::new(max_iterations, precision)
                 .with_population_size(pop_size);
+            if let Some(cp) = constraint_penalty {
+                pso = pso.with_constraint_penalty(cp);
+            }
             (
                 Box::new(pso),
                 format!("Auto: {} params → PSO (pop={}, robust for noisy circuits)", n, pop_size)
@@ -62,22 +119,36 @@ pub fn select_solver(
 
         // Large problems (9+ params) or poorly scaled -> CMA-ES
         (n, _, var, _) if n >= 9 || var > 1.5 => {
+            let mut cmaes = CMAESOptimizer::new(max_iterations, precision);
+            if let Some(cp) = constraint_penalty {
+                cmaes = cmaes.with_constraint_penalty(cp);
+            }
             (
-                Box::new(CMAESOptimizer::new(max_iterations, precision)),
+                Box::new(cmaes),
                 format!("Auto: Large problem ({} params, scale var: {:.2}) → CMA-ES (adaptive)", n, var)
             )
         },
 
         // Default fallback -> PSO (most robust for circuits)
         (n, _, _, _) => {
+            let mut pso = ParticleOptimizer::new(max_iterations, precision)
+                .with_population_size(20);
+            if let Some(cp) = constraint_penalty {
+                pso = pso.with_constraint_penalty(cp);
+            }
             (
-                Box::new(ParticleOptimizer::new(max_iterations, precision)
-                    .with_population_size(20)),
+                Box::new(pso),
                 format!("Auto: {} params → PSO (default, handles noise well)", n)
             )
         }
     };
-    
+
+    let solver: Box<dyn Solver> = if polish {
+        Box::new(PolishingSolver::new(solver, max_iterations, precision))
+    } else {
+        solver
+    };
+
     (solver, reason)
 }
 
@@ -1,4 +1,5 @@
-use super::traits::{OptimizationCallback, Problem, Solver, SolverResult};
+use super::traits::{OptimizationCallback, Problem, Solver, SolverResult, StopReason};
+use std::ops::ControlFlow;
 
 /// Adaptive Newton optimizer with Armijo line search and learning rate adaptation
 pub struct NewtonOptimizer {
@@ -38,7 +39,11 @@ impl NewtonOptimizer {
         }
     }
 
-    /// Compute numerical gradient using central finite differences
+    /// Compute numerical gradient using central finite differences. All `2n`
+    /// perturbation probes are independent of each other, so they're costed
+    /// as a single batch - a problem with a worker pool can fan them out
+    /// across multiple NgSpice instances instead of probing one parameter
+    /// at a time.
     fn compute_gradient(
         &self,
         problem: &dyn Problem,
@@ -49,19 +54,22 @@ impl NewtonOptimizer {
         let h = 1e-6;
         let n = params.len();
 
+        let mut probes = Vec::with_capacity(2 * n);
         for i in 0..n {
             let mut p_plus = params.to_vec();
             let mut p_minus = params.to_vec();
-
             p_plus[i] += h;
             p_minus[i] -= h;
+            probes.push(p_plus);
+            probes.push(p_minus);
+        }
 
-            let c_plus = problem.cost(&p_plus)?;
-            *cost_evals += 1;
-
-            let c_minus = problem.cost(&p_minus)?;
-            *cost_evals += 1;
+        let costs = problem.cost_batch(&probes)?;
+        *cost_evals += costs.len();
 
+        for i in 0..n {
+            let c_plus = costs[2 * i];
+            let c_minus = costs[2 * i + 1];
             grad[i] = (c_plus - c_minus) / (2.0 * h);
         }
 
@@ -140,14 +148,16 @@ impl Solver for NewtonOptimizer {
             let cost = problem.cost(&params)?;
             cost_evals += 1;
 
-            callback.on_iteration(iter + 1, &params, cost)?;
-
-            if callback.should_stop() {
+            if let ControlFlow::Break(reason) = callback.on_iteration(iter + 1, &params, cost) {
+                if let StopReason::SimulationError(e) = reason {
+                    return Err(e);
+                }
                 return Ok(SolverResult {
-                    success: true,
+                    success: reason.is_success(),
                     cost,
                     iterations: iter + 1,
-                    message: "Stopped by callback".into(),
+                    message: reason.message(),
+                    max_violation: problem.max_constraint_violation(&params),
                     params,
                     cost_evals,
                     grad_evals,
@@ -160,6 +170,7 @@ impl Solver for NewtonOptimizer {
                     cost,
                     iterations: iter + 1,
                     message: "Converged".into(),
+                    max_violation: problem.max_constraint_violation(&params),
                     params,
                     cost_evals,
                     grad_evals,
@@ -172,6 +183,7 @@ impl Solver for NewtonOptimizer {
                     cost,
                     iterations: iter + 1,
                     message: "Stagnated".into(),
+                    max_violation: problem.max_constraint_violation(&params),
                     params,
                     cost_evals,
                     grad_evals,
@@ -213,6 +225,7 @@ impl Solver for NewtonOptimizer {
             cost: prev_cost,
             iterations: self.max_iter,
             message: "Max iterations reached".into(),
+            max_violation: problem.max_constraint_violation(&params),
             params,
             cost_evals,
             grad_evals,
@@ -1,5 +1,8 @@
-use super::traits::{OptimizationCallback, Problem, Solver, SolverResult};
+use super::constraint_penalty::{self, ConstraintPenalty};
+use super::sampling::{sample_population, InitMode};
+use super::traits::{OptimizationCallback, Problem, Solver, SolverResult, StopReason};
 use rand::Rng;
+use std::ops::ControlFlow;
 
 /// Particle Swarm Optimization - often outperforms gradient-based methods
 /// for noisy, non-convex problems with fewer cost evaluations
@@ -10,6 +13,15 @@ pub struct ParticleOptimizer {
     inertia: f64,   // w - velocity inertia weight
     cognitive: f64, // c1 - personal best influence
     social: f64,    // c2 - global best influence
+    /// Caps the rayon thread pool each generation's `cost_batch` call is
+    /// run under, see [`ParticleOptimizer::with_workers`]. `None` uses
+    /// rayon's global pool (defaults to one thread per core).
+    threads: Option<usize>,
+    /// How the initial swarm is seeded, see [`ParticleOptimizer::with_init`].
+    init_mode: InitMode,
+    /// Added to each particle's cost once computed, see
+    /// [`ParticleOptimizer::with_constraint_penalty`].
+    constraint_penalty: Option<ConstraintPenalty>,
 }
 
 impl ParticleOptimizer {
@@ -21,6 +33,9 @@ impl ParticleOptimizer {
             inertia: 0.7,
             cognitive: 1.5,
             social: 1.5,
+            threads: None,
+            init_mode: InitMode::Uniform,
+            constraint_penalty: None,
         }
     }
 
@@ -38,6 +53,34 @@ impl ParticleOptimizer {
         self
     }
 
+    /// Cap swarm cost evaluation to `n` concurrent threads instead of
+    /// rayon's default of one per core - useful to bound how many
+    /// simulator instances a noisy-cost [`Problem`] ends up running at
+    /// once. Only affects problems whose `cost_batch` actually parallelizes
+    /// over rayon (the trait default); [`crate::optimization::problem::CircuitProblem`]
+    /// fans out across its own subprocess `WorkerPool` instead, sized by
+    /// `with_worker_pool`.
+    pub fn with_workers(mut self, n: usize) -> Self {
+        self.threads = Some(n.max(1));
+        self
+    }
+
+    /// Configure how the initial swarm covers the bounded space (default:
+    /// `Uniform`, i.e. unchanged behavior). `LatinHypercube` stratifies each
+    /// dimension so the first generation can't clump.
+    pub fn with_init(mut self, mode: InitMode) -> Self {
+        self.init_mode = mode;
+        self
+    }
+
+    /// Fold a [`ConstraintPenalty`] into every particle's cost once it's
+    /// computed, so `ParameterConstraint` relationships the problem doesn't
+    /// hard-project influence which particle the swarm chases.
+    pub fn with_constraint_penalty(mut self, penalty: ConstraintPenalty) -> Self {
+        self.constraint_penalty = Some(penalty);
+        self
+    }
+
     #[inline]
     fn clamp_params(&self, params: &mut [f64], bounds: &[(f64, f64)]) {
         for (i, &(min, max)) in bounds.iter().enumerate() {
@@ -45,28 +88,20 @@ impl ParticleOptimizer {
         }
     }
 
-    /// Initialize particle positions uniformly within bounds
-    fn initialize_particles(
-        &self,
-        n_params: usize,
-        bounds: &[(f64, f64)],
-        initial_params: &[f64],
-    ) -> Vec<Vec<f64>> {
+    /// Initialize particle positions within bounds, per `init_mode`.
+    fn initialize_particles(&self, bounds: &[(f64, f64)], initial_params: &[f64]) -> Vec<Vec<f64>> {
         let mut rng = rand::thread_rng();
-        let mut particles = Vec::with_capacity(self.population_size);
 
-        // First particle is the provided initial guess
+        // First particle is the provided initial guess; the rest cover the
+        // bounded space per `init_mode`.
+        let mut particles = Vec::with_capacity(self.population_size);
         particles.push(initial_params.to_vec());
-
-        // Rest are random within bounds
-        for _ in 1..self.population_size {
-            let mut particle = vec![0.0; n_params];
-            for i in 0..n_params {
-                let (min, max) = bounds[i];
-                particle[i] = rng.gen_range(min..=max);
-            }
-            particles.push(particle);
-        }
+        particles.extend(sample_population(
+            self.init_mode,
+            self.population_size - 1,
+            bounds,
+            &mut rng,
+        ));
 
         particles
     }
@@ -105,8 +140,18 @@ impl Solver for ParticleOptimizer {
         let bounds = problem.bounds();
         let mut rng = rand::thread_rng();
 
+        let thread_pool = match self.threads {
+            Some(n) => Some(
+                rayon::ThreadPoolBuilder::new()
+                    .num_threads(n)
+                    .build()
+                    .map_err(|e| format!("Failed to build thread pool: {}", e))?,
+            ),
+            None => None,
+        };
+
         // Initialize swarm
-        let mut particles = self.initialize_particles(n, bounds, problem.initial_params());
+        let mut particles = self.initialize_particles(bounds, problem.initial_params());
         let mut velocities = self.initialize_velocities(n, bounds);
         let mut personal_best_positions = particles.clone();
         let mut personal_best_costs = vec![f64::INFINITY; self.population_size];
@@ -122,15 +167,27 @@ impl Solver for ParticleOptimizer {
         for iter in 0..self.max_iter {
             let prev_global_best = global_best_cost;
 
-            // Evaluate all particles
+            // Apply constraints and bounds to the whole swarm before costing it
             for p in 0..self.population_size {
-                // Apply constraints and bounds
                 problem.apply_constraints(&mut particles[p])?;
                 self.clamp_params(&mut particles[p], bounds);
+            }
+
+            // Evaluate the whole generation in one batch (THIS RUNS SIMULATIONS) -
+            // lets a problem with a worker pool fan the swarm out across
+            // multiple NgSpice instances instead of costing particles serially.
+            // Problems without one fall back to the trait's rayon-parallel
+            // default `cost_batch`, which `with_workers` caps by scoping
+            // this call to a bounded pool.
+            let mut costs = match &thread_pool {
+                Some(pool) => pool.install(|| problem.cost_batch(&particles))?,
+                None => problem.cost_batch(&particles)?,
+            };
+            constraint_penalty::apply_to_batch(self.constraint_penalty.as_ref(), &mut costs, &particles);
+            cost_evals += costs.len();
 
-                // Evaluate cost (THIS RUNS SIMULATION)
-                let cost = problem.cost(&particles[p])?;
-                cost_evals += 1;
+            for p in 0..self.population_size {
+                let cost = costs[p];
 
                 // Update personal best
                 if cost < personal_best_costs[p] {
@@ -146,15 +203,23 @@ impl Solver for ParticleOptimizer {
             }
 
             // Report progress using the global best
-            callback.on_iteration(iter + 1, &personal_best_positions[global_best_idx], global_best_cost)?;
+            let iteration_result = callback.on_iteration(
+                iter + 1,
+                &personal_best_positions[global_best_idx],
+                global_best_cost,
+            );
 
             // Check for early termination
-            if callback.should_stop() {
+            if let ControlFlow::Break(reason) = iteration_result {
+                if let StopReason::SimulationError(e) = reason {
+                    return Err(e);
+                }
                 return Ok(SolverResult {
-                    success: true,
+                    success: reason.is_success(),
                     cost: global_best_cost,
                     iterations: iter + 1,
-                    message: "Stopped by callback".into(),
+                    message: reason.message(),
+                    max_violation: problem.max_constraint_violation(&personal_best_positions[global_best_idx]),
                     params: personal_best_positions[global_best_idx].clone(),
                     cost_evals,
                     grad_evals: 0,
@@ -168,6 +233,7 @@ impl Solver for ParticleOptimizer {
                     cost: global_best_cost,
                     iterations: iter + 1,
                     message: "Converged".into(),
+                    max_violation: problem.max_constraint_violation(&personal_best_positions[global_best_idx]),
                     params: personal_best_positions[global_best_idx].clone(),
                     cost_evals,
                     grad_evals: 0,
@@ -183,6 +249,7 @@ impl Solver for ParticleOptimizer {
                         cost: global_best_cost,
                         iterations: iter + 1,
                         message: "Stagnated".into(),
+                        max_violation: problem.max_constraint_violation(&personal_best_positions[global_best_idx]),
                         params: personal_best_positions[global_best_idx].clone(),
                         cost_evals,
                         grad_evals: 0,
@@ -223,6 +290,7 @@ impl Solver for ParticleOptimizer {
             cost: global_best_cost,
             iterations: self.max_iter,
             message: "Max iterations reached".into(),
+            max_violation: problem.max_constraint_violation(&personal_best_positions[global_best_idx]),
             params: personal_best_positions[global_best_idx].clone(),
             cost_evals,
             grad_evals: 0,
@@ -0,0 +1,139 @@
+use super::newton::NewtonOptimizer;
+use super::traits::{BatchHandle, OptimizationCallback, Problem, Solver, SolverResult};
+
+/// Wraps a target [`Problem`], overriding only its starting point - lets
+/// [`NewtonOptimizer`] be launched from wherever a global solver left off
+/// instead of from `problem.initial_params()`.
+struct SeededProblem<'a> {
+    target: &'a dyn Problem,
+    start: Vec<f64>,
+}
+
+impl<'a> Problem for SeededProblem<'a> {
+    fn cost(&self, params: &[f64]) -> Result<f64, String> {
+        self.target.cost(params)
+    }
+
+    fn num_params(&self) -> usize {
+        self.target.num_params()
+    }
+
+    fn initial_params(&self) -> &[f64] {
+        &self.start
+    }
+
+    fn bounds(&self) -> &[(f64, f64)] {
+        self.target.bounds()
+    }
+
+    fn apply_constraints(&self, params: &mut [f64]) -> Result<(), String> {
+        self.target.apply_constraints(params)
+    }
+
+    fn lower_bounds(&self) -> Option<Vec<f64>> {
+        self.target.lower_bounds()
+    }
+
+    fn upper_bounds(&self) -> Option<Vec<f64>> {
+        self.target.upper_bounds()
+    }
+
+    fn max_constraint_violation(&self, params: &[f64]) -> f64 {
+        self.target.max_constraint_violation(params)
+    }
+
+    fn cost_batch(&self, candidates: &[Vec<f64>]) -> Result<Vec<f64>, String> {
+        self.target.cost_batch(candidates)
+    }
+
+    fn submit_batch(&self, candidates: Vec<Vec<f64>>) -> BatchHandle {
+        self.target.submit_batch(candidates)
+    }
+}
+
+/// Wraps any [`Solver`] with an optional local-refinement pass: once the
+/// inner solver returns, launches [`NewtonOptimizer`] from its best point to
+/// sharpen convergence in the final basin, keeping the polished result only
+/// if it actually improves on the primary solver's. Global stochastic
+/// solvers (PSO, CMA-ES, DE) get close to the optimum but waste iterations
+/// fine-tuning there - this mirrors the common "global search then gradient
+/// polish" workflow as a solver-agnostic wrapper, rather than re-deriving it
+/// per solver the way [`super::HybridOptimizer`] interleaves refinement into
+/// PSO specifically.
+pub struct PolishingSolver {
+    inner: Box<dyn Solver>,
+    enabled: bool,
+    newton_max_iter: u32,
+    newton_precision: f64,
+}
+
+impl PolishingSolver {
+    /// `newton_max_iter`/`newton_precision` bound the polishing pass;
+    /// polishing is on by default, see [`PolishingSolver::with_polish`].
+    pub fn new(inner: Box<dyn Solver>, newton_max_iter: u32, newton_precision: f64) -> Self {
+        Self {
+            inner,
+            enabled: true,
+            newton_max_iter,
+            newton_precision,
+        }
+    }
+
+    /// Toggle the local-refinement pass without discarding the wrapper -
+    /// `with_polish(false)` makes `solve` a pure pass-through to the inner
+    /// solver.
+    pub fn with_polish(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+}
+
+impl Solver for PolishingSolver {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn solve(
+        &mut self,
+        problem: &dyn Problem,
+        callback: &mut dyn OptimizationCallback,
+    ) -> Result<SolverResult, String> {
+        let primary = self.inner.solve(problem, callback)?;
+
+        if !self.enabled {
+            return Ok(primary);
+        }
+
+        let seeded = SeededProblem {
+            target: problem,
+            start: primary.params.clone(),
+        };
+        let mut newton = NewtonOptimizer::new(self.newton_max_iter, self.newton_precision);
+        let polished = newton.solve(&seeded, callback)?;
+
+        let cost_evals = primary.cost_evals + polished.cost_evals;
+        let grad_evals = primary.grad_evals + polished.grad_evals;
+        let iterations = primary.iterations + polished.iterations;
+
+        if polished.cost < primary.cost {
+            Ok(SolverResult {
+                success: polished.success || primary.success,
+                cost: polished.cost,
+                iterations,
+                message: format!("{} -> polished by Newton ({})", primary.message, polished.message),
+                max_violation: polished.max_violation,
+                params: polished.params,
+                cost_evals,
+                grad_evals,
+            })
+        } else {
+            Ok(SolverResult {
+                cost_evals,
+                grad_evals,
+                iterations,
+                message: format!("{} (polish did not improve)", primary.message),
+                ..primary
+            })
+        }
+    }
+}
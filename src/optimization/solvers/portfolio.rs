@@ -0,0 +1,196 @@
+use super::cma_es::CMAESOptimizer;
+use super::differential_evolution::DifferentialEvolutionOptimizer;
+use super::particle::ParticleOptimizer;
+use super::traits::{OptimizationCallback, Problem, Solver, SolverResult, StopReason};
+use std::ops::ControlFlow;
+use std::sync::{Arc, Mutex};
+
+/// Per-racer callback [`PortfolioSolver::solve`] hands to each solver in the
+/// race: checks the shared stop flag on every iteration so a winner halts
+/// its rivals immediately, and claims that flag itself the moment its own
+/// cost reaches `precision` - applied uniformly here rather than relying on
+/// each solver's own convergence check, since what "precision" means
+/// internally (gradient norm, population spread, ...) differs solver to
+/// solver.
+struct RacingCallback {
+    stop: Arc<Mutex<bool>>,
+    precision: f64,
+}
+
+impl OptimizationCallback for RacingCallback {
+    fn on_iteration(
+        &mut self,
+        _iteration: u32,
+        _params: &[f64],
+        cost: f64,
+    ) -> ControlFlow<StopReason, ()> {
+        let mut stop = self.stop.lock().unwrap();
+        if *stop {
+            return ControlFlow::Break(StopReason::UserInterrupt(
+                "stopped: another portfolio strategy won".to_string(),
+            ));
+        }
+        if cost <= self.precision {
+            *stop = true;
+            return ControlFlow::Break(StopReason::Converged);
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// Wraps a target [`Problem`] so every whole-candidate cost evaluation (one
+/// `cost()` call, or one `cost_batch()` generation) runs under a lock shared
+/// across every racer in a [`PortfolioSolver`]. `Problem: Sync` only
+/// promises a racer's `cost()` call is memory-safe to make concurrently with
+/// another's, not that the *result* is meaningful:
+/// [`crate::optimization::problem::CircuitProblem`]'s interactive path
+/// drives a single in-process ngspice singleton through a
+/// set-parameters/run/read-metrics sequence that takes and drops its
+/// `Mutex<NgSpice>` partway through, so two racers' calls can freely
+/// interleave their simulation runs and each read back a mix of the other's
+/// state. Serializing whole evaluations behind one lock keeps the racers
+/// themselves concurrent (sampling, recombination, bookkeeping) while
+/// guaranteeing only one candidate is ever being simulated at a time,
+/// whichever racer asked for it - the same constraint a single generation's
+/// own candidates already run under when no worker pool is attached.
+struct SerializedProblem<'a> {
+    target: &'a dyn Problem,
+    lock: Arc<Mutex<()>>,
+}
+
+impl<'a> Problem for SerializedProblem<'a> {
+    fn cost(&self, params: &[f64]) -> Result<f64, String> {
+        let _guard = self.lock.lock().unwrap();
+        self.target.cost(params)
+    }
+
+    fn num_params(&self) -> usize {
+        self.target.num_params()
+    }
+
+    fn initial_params(&self) -> &[f64] {
+        self.target.initial_params()
+    }
+
+    fn bounds(&self) -> &[(f64, f64)] {
+        self.target.bounds()
+    }
+
+    fn apply_constraints(&self, params: &mut [f64]) -> Result<(), String> {
+        self.target.apply_constraints(params)
+    }
+
+    fn lower_bounds(&self) -> Option<Vec<f64>> {
+        self.target.lower_bounds()
+    }
+
+    fn upper_bounds(&self) -> Option<Vec<f64>> {
+        self.target.upper_bounds()
+    }
+
+    fn max_constraint_violation(&self, params: &[f64]) -> f64 {
+        self.target.max_constraint_violation(params)
+    }
+
+    fn cost_batch(&self, candidates: &[Vec<f64>]) -> Result<Vec<f64>, String> {
+        let _guard = self.lock.lock().unwrap();
+        self.target.cost_batch(candidates)
+    }
+}
+
+/// Races several solvers against the same problem instead of committing to
+/// the single pick [`super::select_solver`]'s heuristics make, which can
+/// misjudge an unusual circuit. Each racer runs on its own OS thread behind
+/// a [`SerializedProblem`] (so simulations don't interleave, see its doc)
+/// and a [`RacingCallback`] that shares one `Arc<Mutex<bool>>` stop flag:
+/// the first racer to reach `precision` sets the flag, and every other racer
+/// notices on its very next iteration and stops there instead of running to
+/// its own completion. A racer that returns without reaching `precision`
+/// leaves the flag unset, so the others keep running. The [`SolverResult`] with
+/// the lowest cost wins, with `message` naming which strategy it came from.
+pub struct PortfolioSolver {
+    solvers: Vec<Box<dyn Solver + Send>>,
+    precision: f64,
+}
+
+impl PortfolioSolver {
+    /// `precision` is the shared target every racer's [`RacingCallback`]
+    /// checks its cost against - independent of whatever precision each
+    /// solver was individually constructed with.
+    pub fn new(solvers: Vec<Box<dyn Solver + Send>>, precision: f64) -> Self {
+        Self { solvers, precision }
+    }
+
+    /// A reasonable default portfolio for circuit optimization: PSO (robust,
+    /// general-purpose), CMA-ES (adaptive, handles poorly-scaled parameters)
+    /// and Differential Evolution (tolerant of multimodal, noisy costs) -
+    /// the same three gradient-free families [`super::select_solver`] picks
+    /// between, raced together instead of chosen by heuristic.
+    pub fn default_trio(max_iterations: u32, precision: f64) -> Self {
+        Self::new(
+            vec![
+                Box::new(ParticleOptimizer::new(max_iterations, precision)),
+                Box::new(CMAESOptimizer::new(max_iterations, precision)),
+                Box::new(DifferentialEvolutionOptimizer::new(max_iterations, precision)),
+            ],
+            precision,
+        )
+    }
+}
+
+impl Solver for PortfolioSolver {
+    fn name(&self) -> &str {
+        "Portfolio"
+    }
+
+    fn solve(
+        &mut self,
+        problem: &dyn Problem,
+        callback: &mut dyn OptimizationCallback,
+    ) -> Result<SolverResult, String> {
+        let stop = Arc::new(Mutex::new(false));
+        let eval_lock = Arc::new(Mutex::new(()));
+        let precision = self.precision;
+
+        let results: Vec<(String, Result<SolverResult, String>)> = std::thread::scope(|scope| {
+            let mut handles = Vec::with_capacity(self.solvers.len());
+
+            for solver in &mut self.solvers {
+                let name = solver.name().to_string();
+                let stop = Arc::clone(&stop);
+                let serialized = SerializedProblem {
+                    target: problem,
+                    lock: Arc::clone(&eval_lock),
+                };
+                let handle = scope.spawn(move || {
+                    let mut racing_cb = RacingCallback { stop, precision };
+                    solver.solve(&serialized, &mut racing_cb)
+                });
+                handles.push((name, handle));
+            }
+
+            handles
+                .into_iter()
+                .map(|(name, handle)| (name, handle.join().expect("portfolio racer panicked")))
+                .collect()
+        });
+
+        let (winner_name, winner) = results
+            .into_iter()
+            .filter_map(|(name, result)| result.ok().map(|res| (name, res)))
+            .min_by(|(_, a), (_, b)| {
+                a.cost.partial_cmp(&b.cost).unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .ok_or_else(|| "every portfolio strategy failed".to_string())?;
+
+        // Report the winner as a single final iteration - there's no single
+        // iteration count to forward progress from while racers run on
+        // separate threads.
+        let _ = callback.on_iteration(winner.iterations, &winner.params, winner.cost);
+
+        Ok(SolverResult {
+            message: format!("Portfolio: {} won ({})", winner_name, winner.message),
+            ..winner
+        })
+    }
+}
@@ -0,0 +1,190 @@
+use super::traits::{OptimizationCallback, Problem, Solver, SolverResult, StopReason};
+use rand::Rng;
+use rand_distr::{Distribution, StandardNormal};
+use std::ops::ControlFlow;
+
+/// How the temperature `T` decays over the run. All three are common
+/// Metropolis-schedule choices; exponential is the default because it
+/// cools fastest while still giving early iterations a wide acceptance
+/// window.
+#[derive(Clone, Copy, Debug)]
+pub enum TempSchedule {
+    /// `T = T0 * r^iter`
+    Exponential { r: f64 },
+    /// `T = T0 * (1 - iter / max_iter)`
+    Linear,
+    /// `T = T0 / ln(iter + 2)`
+    Boltzmann,
+}
+
+impl TempSchedule {
+    fn temperature(&self, t0: f64, iter: u32, max_iter: u32) -> f64 {
+        match *self {
+            TempSchedule::Exponential { r } => t0 * r.powi(iter as i32),
+            TempSchedule::Linear => {
+                let frac = 1.0 - (iter as f64 / max_iter.max(1) as f64);
+                t0 * frac.max(0.0)
+            }
+            TempSchedule::Boltzmann => t0 / ((iter as f64 + 2.0).ln()),
+        }
+    }
+}
+
+/// Simulated Annealing - a single-point Metropolis search that tolerates
+/// rugged, multimodal cost landscapes where gradients mislead and where
+/// `ParticleOptimizer`'s population cost is unaffordable.
+pub struct SimulatedAnnealing {
+    max_iter: u32,
+    precision: f64,
+    initial_temp: f64,
+    schedule: TempSchedule,
+    step_scale: f64,
+}
+
+impl SimulatedAnnealing {
+    pub fn new(max_iter: u32, precision: f64) -> Self {
+        Self {
+            max_iter,
+            precision,
+            initial_temp: 1.0,
+            schedule: TempSchedule::Exponential { r: 0.95 },
+            step_scale: 0.1,
+        }
+    }
+
+    /// Configure the starting temperature `T0` (default: 1.0).
+    pub fn with_initial_temp(mut self, t0: f64) -> Self {
+        self.initial_temp = t0;
+        self
+    }
+
+    /// Configure the cooling schedule (default: exponential, r=0.95).
+    pub fn with_schedule(mut self, schedule: TempSchedule) -> Self {
+        self.schedule = schedule;
+        self
+    }
+
+    /// Configure the neighbor-perturbation step as a fraction of each
+    /// parameter's `(max - min)` range (default: 0.1).
+    pub fn with_step_scale(mut self, step_scale: f64) -> Self {
+        self.step_scale = step_scale;
+        self
+    }
+
+    #[inline]
+    fn clamp_params(&self, params: &mut [f64], bounds: &[(f64, f64)]) {
+        for (i, &(min, max)) in bounds.iter().enumerate() {
+            params[i] = params[i].clamp(min, max);
+        }
+    }
+
+    /// Perturb every parameter with a Gaussian step scaled to a fraction of
+    /// its range, then clamp to bounds.
+    fn neighbor(&self, params: &[f64], bounds: &[(f64, f64)], rng: &mut impl Rng) -> Vec<f64> {
+        let mut next = params.to_vec();
+        for (i, &(min, max)) in bounds.iter().enumerate() {
+            let step: f64 = StandardNormal.sample(rng);
+            next[i] += step * self.step_scale * (max - min);
+        }
+        self.clamp_params(&mut next, bounds);
+        next
+    }
+}
+
+impl Solver for SimulatedAnnealing {
+    fn name(&self) -> &str {
+        "SimulatedAnnealing"
+    }
+
+    fn solve(
+        &mut self,
+        problem: &dyn Problem,
+        callback: &mut dyn OptimizationCallback,
+    ) -> Result<SolverResult, String> {
+        let bounds = problem.bounds();
+        let mut rng = rand::thread_rng();
+
+        let mut cur_params = problem.initial_params().to_vec();
+        problem.apply_constraints(&mut cur_params)?;
+        self.clamp_params(&mut cur_params, bounds);
+
+        let mut cost_evals = 0usize;
+        let mut cur_cost = problem.cost(&cur_params)?;
+        cost_evals += 1;
+
+        let mut best_params = cur_params.clone();
+        let mut best_cost = cur_cost;
+
+        for iter in 0..self.max_iter {
+            if let ControlFlow::Break(reason) =
+                callback.on_iteration(iter + 1, &best_params, best_cost)
+            {
+                if let StopReason::SimulationError(e) = reason {
+                    return Err(e);
+                }
+                return Ok(SolverResult {
+                    success: reason.is_success(),
+                    cost: best_cost,
+                    iterations: iter + 1,
+                    message: reason.message(),
+                    max_violation: problem.max_constraint_violation(&best_params),
+                    params: best_params,
+                    cost_evals,
+                    grad_evals: 0,
+                });
+            }
+
+            if best_cost < self.precision {
+                return Ok(SolverResult {
+                    success: true,
+                    cost: best_cost,
+                    iterations: iter + 1,
+                    message: "Converged".into(),
+                    max_violation: problem.max_constraint_violation(&best_params),
+                    params: best_params,
+                    cost_evals,
+                    grad_evals: 0,
+                });
+            }
+
+            let temp = self.schedule.temperature(self.initial_temp, iter, self.max_iter);
+
+            let mut candidate = self.neighbor(&cur_params, bounds, &mut rng);
+            problem.apply_constraints(&mut candidate)?;
+            self.clamp_params(&mut candidate, bounds);
+
+            let candidate_cost = problem.cost(&candidate)?;
+            cost_evals += 1;
+
+            let accept = if candidate_cost < cur_cost {
+                true
+            } else if temp > 0.0 {
+                let p = (-(candidate_cost - cur_cost) / temp).exp();
+                rng.gen::<f64>() < p
+            } else {
+                false
+            };
+
+            if accept {
+                cur_params = candidate;
+                cur_cost = candidate_cost;
+
+                if cur_cost < best_cost {
+                    best_cost = cur_cost;
+                    best_params = cur_params.clone();
+                }
+            }
+        }
+
+        Ok(SolverResult {
+            success: false,
+            cost: best_cost,
+            iterations: self.max_iter,
+            message: "Max iterations reached".into(),
+            max_violation: problem.max_constraint_violation(&best_params),
+            params: best_params,
+            cost_evals,
+            grad_evals: 0,
+        })
+    }
+}
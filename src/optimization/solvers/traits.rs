@@ -7,21 +7,70 @@ pub struct SolverResult {
     pub params: Vec<f64>,
     pub cost_evals: usize,
     pub grad_evals: usize,
+    /// Largest feasibility-subsystem constraint violation at `params`, so
+    /// callers can tell a feasible optimum from a merely penalized one.
+    /// Zero when the problem has no CSP-style constraints registered.
+    pub max_violation: f64,
 }
 
-/// Callback interface for optimization progress
-pub trait OptimizationCallback {
-    /// Called at each iteration with current parameters and cost
-    fn on_iteration(&mut self, iteration: u32, params: &[f64], cost: f64) -> Result<(), String>;
+/// Why an [`OptimizationCallback`] asked the solver to stop, threaded
+/// straight through [`std::ops::ControlFlow::Break`] instead of a separate
+/// `should_stop` poll - the callback can now raise a convergence, time
+/// budget, or interrupt signal from the very call that reports the
+/// iteration, instead of splitting that decision across two methods.
+#[derive(Clone, Debug)]
+pub enum StopReason {
+    /// A stop criterion fired (target cost, tolerance, stagnation, all
+    /// targets met, ...) - the run found what it was looking for.
+    Converged,
+    /// The iteration cap was reached without converging.
+    MaxIterations,
+    /// The wall-clock time budget was reached without converging.
+    TimeBudget,
+    /// The user interrupted the run (e.g. Ctrl+C).
+    UserInterrupt(String),
+    /// The simulation backing the cost evaluation failed.
+    SimulationError(String),
+}
 
-    /// Check if optimization should stop early
-    fn should_stop(&self) -> bool {
-        false
+impl StopReason {
+    /// Whether this counts as a successful stop for [`SolverResult::success`].
+    pub fn is_success(&self) -> bool {
+        matches!(self, StopReason::Converged)
     }
+
+    /// Human-readable message for [`SolverResult::message`].
+    pub fn message(&self) -> String {
+        match self {
+            StopReason::Converged => "Converged".to_string(),
+            StopReason::MaxIterations => "Maximum iterations reached".to_string(),
+            StopReason::TimeBudget => "Time budget exceeded".to_string(),
+            StopReason::UserInterrupt(msg) => msg.clone(),
+            StopReason::SimulationError(msg) => msg.clone(),
+        }
+    }
+}
+
+/// Callback interface for optimization progress
+pub trait OptimizationCallback {
+    /// Called at each iteration with current parameters and cost. Returning
+    /// [`std::ops::ControlFlow::Break`] asks the solver to stop now, with
+    /// the enclosed [`StopReason`] flowing straight into its `SolverResult`.
+    fn on_iteration(
+        &mut self,
+        iteration: u32,
+        params: &[f64],
+        cost: f64,
+    ) -> std::ops::ControlFlow<StopReason, ()>;
 }
 
 /// Core problem definition - just the essentials
-pub trait Problem {
+///
+/// `Sync` so a solver can fan `cost()` calls for independent candidates out
+/// across a thread pool (see [`crate::optimization::solvers::ParticleOptimizer`]'s
+/// rayon-parallel swarm evaluation) instead of only ever costing one
+/// candidate at a time.
+pub trait Problem: Sync {
     /// Evaluate cost for given parameters (runs simulation)
     fn cost(&self, params: &[f64]) -> Result<f64, String>;
 
@@ -36,6 +85,88 @@ pub trait Problem {
 
     /// Apply constraints to parameters (modifies params in place)
     fn apply_constraints(&self, params: &mut [f64]) -> Result<(), String>;
+
+    /// Per-parameter lower bounds, when the problem can expose them
+    /// independently of `bounds()`. Solvers that understand box
+    /// constraints (e.g. L-BFGS-B) should prefer this over clamping;
+    /// solvers that don't can ignore it and fall back to `bounds()`.
+    fn lower_bounds(&self) -> Option<Vec<f64>> {
+        None
+    }
+
+    /// Per-parameter upper bounds, see [`Problem::lower_bounds`].
+    fn upper_bounds(&self) -> Option<Vec<f64>> {
+        None
+    }
+
+    /// Largest violation magnitude across any CSP-style [`crate::core::constraints::Constraint`]
+    /// the problem carries. Zero means feasible (or no constraints registered).
+    fn max_constraint_violation(&self, _params: &[f64]) -> f64 {
+        0.0
+    }
+
+    /// Evaluate cost for a whole generation of candidates at once. Problems
+    /// that can fan simulations out across a worker pool (see
+    /// [`crate::optimization::worker_pool::WorkerPool`]) should override this
+    /// for population-based solvers (PSO, CMA-ES) - [`crate::optimization::problem::CircuitProblem`]
+    /// does, since its interactive `cost()` path drives a single
+    /// process-wide `NgSpice` instance that can't be called concurrently
+    /// (see the [`crate::optimization::problem_pool`] module doc). Problems
+    /// without a worker pool to fan out across (e.g. a bare closure wrapped
+    /// for [`crate::optimization::minimize::minimize`]) get a rayon-parallel
+    /// map over `cost()` here for free - each candidate is independent, so
+    /// this is safe precisely because [`Problem`] requires `Sync`.
+    fn cost_batch(&self, candidates: &[Vec<f64>]) -> Result<Vec<f64>, String> {
+        use rayon::prelude::*;
+        candidates.par_iter().map(|params| self.cost(params)).collect()
+    }
+
+    /// Schedule `candidates` for cost evaluation without blocking the
+    /// caller, returning a [`BatchHandle`] to `poll`/`join` later - lets a
+    /// population-based solver submit a whole generation and go do other
+    /// bookkeeping (selection, recombination of the *previous* generation)
+    /// while the worker pool saturates on this one. The default wraps
+    /// [`Problem::cost_batch`] in an already-finished handle, so problems
+    /// that don't override this keep working exactly as before.
+    fn submit_batch(&self, candidates: Vec<Vec<f64>>) -> BatchHandle {
+        let len = candidates.len();
+        BatchHandle::ready(self.cost_batch(&candidates), len)
+    }
+}
+
+/// Handle to an in-flight [`Problem::submit_batch`] call - `poll` checks
+/// without blocking, `join` blocks until every candidate in the batch has
+/// been costed and returns them in submission order. Modeled on a
+/// send-without-waiting client: `submit_batch` is the "send", `join` is the
+/// "confirm".
+pub struct BatchHandle {
+    inner: std::thread::JoinHandle<Vec<Result<f64, String>>>,
+}
+
+impl BatchHandle {
+    /// Wrap an already-computed batch result (or a shared per-candidate
+    /// error) in an already-finished handle, for [`Problem::submit_batch`]'s
+    /// default and any problem without real concurrency to fall back on.
+    pub(crate) fn ready(results: Result<Vec<f64>, String>, len: usize) -> Self {
+        let costs: Vec<Result<f64, String>> = match results {
+            Ok(costs) => costs.into_iter().map(Ok).collect(),
+            Err(e) => (0..len).map(|_| Err(e.clone())).collect(),
+        };
+        Self {
+            inner: std::thread::spawn(move || costs),
+        }
+    }
+
+    /// True once every candidate in the batch has been costed.
+    pub fn poll(&self) -> bool {
+        self.inner.is_finished()
+    }
+
+    /// Block until every candidate in the batch has been costed, returning
+    /// costs in submission order.
+    pub fn join(self) -> Vec<Result<f64, String>> {
+        self.inner.join().expect("batch worker thread panicked")
+    }
 }
 
 /// Solver interface - takes problem and callback
@@ -104,16 +235,17 @@ pub trait Solver {
 //            let cost = problem.cost(&params)?;
 //            cost_evals += 1;
 //
-//            // Step 3: Notify callback (displays progress, tracks history)
-//            callback.on_iteration(iter + 1, &params, cost)?;
-//
-//            // Step 4: Check stopping conditions
-//            if callback.should_stop() {
+//            // Step 3: Notify callback and check for an early stop in one call
+//            if let ControlFlow::Break(reason) = callback.on_iteration(iter + 1, &params, cost) {
+//                if let StopReason::SimulationError(e) = reason {
+//                    return Err(e);
+//                }
 //                return Ok(SolverResult {
-//                    success: true,
+//                    success: reason.is_success(),
 //                    cost,
 //                    iterations: iter + 1,
-//                    message: "Stopped by callback".into(),
+//                    message: reason.message(),
+//                    max_violation: problem.max_constraint_violation(&params),
 //                    params,
 //                    cost_evals,
 //                    grad_evals: 0, // or your count
@@ -148,7 +280,7 @@ pub trait Solver {
 //    - Always call callback.on_iteration() after evaluating cost
 //    - Always apply constraints before evaluating cost
 //    - Track cost_evals to report how many simulations were run
-//    - Use callback.should_stop() to respect max iteration limits
+//    - Stop as soon as callback.on_iteration() returns ControlFlow::Break
 //
 // 5. EXAMPLE OPTIMIZERS TO IMPLEMENT:
 //
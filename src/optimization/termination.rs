@@ -0,0 +1,153 @@
+//! Composable stopping criteria for an optimization run.
+//!
+//! [`CircuitOptimizationCallback`](super::callback::CircuitOptimizationCallback)
+//! used to decide `should_stop` purely by comparing `iteration_count` against
+//! `max_iterations`, which burns simulations the run has already converged.
+//! Instead it holds a `Vec<Box<dyn StopCriterion>>`, checked every iteration
+//! in order; the first one to match sets the run's [`TerminationReason`].
+
+use super::callback::IterationResult;
+use super::observer::TargetStatus;
+use crate::core::TerminationReason;
+use std::time::Duration;
+
+/// Everything a [`StopCriterion`] needs to decide whether to stop.
+pub struct StopContext<'a> {
+    pub iteration: u32,
+    pub cost: f64,
+    pub history: &'a [IterationResult],
+    pub targets: &'a [TargetStatus],
+    /// Wall-clock time elapsed since the run started.
+    pub elapsed: Duration,
+}
+
+/// A single argmin-style stopping rule. Returns the reason it fired, or
+/// `None` if the run should keep going.
+pub trait StopCriterion {
+    fn check(&self, ctx: &StopContext) -> Option<TerminationReason>;
+}
+
+/// Stop once `iteration` reaches `max_iterations` - the original behavior.
+pub struct MaxIterations(pub u32);
+
+impl StopCriterion for MaxIterations {
+    fn check(&self, ctx: &StopContext) -> Option<TerminationReason> {
+        if ctx.iteration >= self.0 {
+            Some(TerminationReason::MaxIters)
+        } else {
+            None
+        }
+    }
+}
+
+/// Stop once the run has been going for at least `max_duration` of
+/// wall-clock time, regardless of iteration count - useful when each
+/// evaluation's cost varies wildly (e.g. NgSpice convergence retries).
+pub struct TimeBudget(pub Duration);
+
+impl StopCriterion for TimeBudget {
+    fn check(&self, ctx: &StopContext) -> Option<TerminationReason> {
+        if ctx.elapsed >= self.0 {
+            Some(TerminationReason::TimeBudgetExceeded)
+        } else {
+            None
+        }
+    }
+}
+
+/// Stop once the cost reaches a user-specified target value.
+pub struct TargetCost(pub f64);
+
+impl StopCriterion for TargetCost {
+    fn check(&self, ctx: &StopContext) -> Option<TerminationReason> {
+        if ctx.cost <= self.0 {
+            Some(TerminationReason::TargetCostReached)
+        } else {
+            None
+        }
+    }
+}
+
+/// Stop once the cost drops below an absolute tolerance near zero.
+pub struct AbsTolerance(pub f64);
+
+impl StopCriterion for AbsTolerance {
+    fn check(&self, ctx: &StopContext) -> Option<TerminationReason> {
+        if ctx.cost < self.0 {
+            Some(TerminationReason::AbsToleranceReached)
+        } else {
+            None
+        }
+    }
+}
+
+/// Stop once consecutive iterations' costs stop changing relative to their
+/// own magnitude: `|cost_k - cost_{k-1}| / max(|cost_k|, eps) < rel_tol`.
+pub struct RelTolerance {
+    pub rel_tol: f64,
+    pub eps: f64,
+}
+
+impl RelTolerance {
+    pub fn new(rel_tol: f64) -> Self {
+        Self { rel_tol, eps: 1e-12 }
+    }
+}
+
+impl StopCriterion for RelTolerance {
+    fn check(&self, ctx: &StopContext) -> Option<TerminationReason> {
+        let len = ctx.history.len();
+        if len < 2 {
+            return None;
+        }
+
+        let prev_cost = ctx.history[len - 2].cost;
+        let change = (ctx.cost - prev_cost).abs() / ctx.cost.abs().max(self.eps);
+        if change < self.rel_tol {
+            Some(TerminationReason::RelToleranceReached)
+        } else {
+            None
+        }
+    }
+}
+
+/// Stop once the best cost hasn't improved by more than `delta` over the
+/// last `window` iterations.
+pub struct Stagnation {
+    pub window: usize,
+    pub delta: f64,
+}
+
+impl StopCriterion for Stagnation {
+    fn check(&self, ctx: &StopContext) -> Option<TerminationReason> {
+        if ctx.history.len() < self.window {
+            return None;
+        }
+
+        let recent = &ctx.history[ctx.history.len() - self.window..];
+        let best_in_window = recent
+            .iter()
+            .map(|r| r.cost)
+            .fold(f64::INFINITY, f64::min);
+        let oldest_in_window = recent[0].cost;
+
+        if oldest_in_window - best_in_window < self.delta {
+            Some(TerminationReason::Stagnation)
+        } else {
+            None
+        }
+    }
+}
+
+/// Stop as soon as every target is satisfied, regardless of cost.
+pub struct AllTargetsMet;
+
+impl StopCriterion for AllTargetsMet {
+    fn check(&self, ctx: &StopContext) -> Option<TerminationReason> {
+        if !ctx.targets.is_empty() && ctx.targets.iter().all(|t| t.satisfied) {
+            Some(TerminationReason::AllTargetsMet)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! Subprocess-backed parallel ngspice evaluation.
+//!
+//! `crate::simulation::NgSpice` wraps the shared `ngspice` library, which is
+//! an in-process singleton: only one circuit can be loaded and simulated at a
+//! time per process. Population-based optimizers want to cost dozens of
+//! candidates per generation, so instead of sharing that singleton, a
+//! [`WorkerPool`] spawns N independent `ngspice` *subprocesses* in batch mode
+//! (`ngspice -b <netlist>`), each reading its own self-contained netlist file
+//! and returning its own captured stdout. Candidates are split across the
+//! pool the way a conventional worker queue splits a slice of jobs across
+//! threads.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// One subprocess-backed ngspice worker: its own scratch netlist file, kept
+/// alive for the life of the pool so repeated batches reuse the same path.
+pub struct NgSpiceWorker {
+    id: usize,
+    netlist_path: PathBuf,
+}
+
+impl NgSpiceWorker {
+    fn new(id: usize) -> Self {
+        let netlist_path = std::env::temp_dir().join(format!(
+            "ngspice_opt_{}_worker{}.spice",
+            std::process::id(),
+            id
+        ));
+        Self { id, netlist_path }
+    }
+
+    /// Write `netlist` to this worker's scratch file and run it to
+    /// completion in ngspice batch mode, returning the captured stdout split
+    /// into lines (the same shape `NGSPICE_OUTPUT` accumulates for the live,
+    /// in-process path).
+    pub fn run(&self, netlist: &[String]) -> Result<Vec<String>, String> {
+        let mut file = std::fs::File::create(&self.netlist_path)
+            .map_err(|e| format!("Worker {}: failed to create temp file: {}", self.id, e))?;
+        for line in netlist {
+            writeln!(file, "{}", line)
+                .map_err(|e| format!("Worker {}: failed to write netlist: {}", self.id, e))?;
+        }
+        drop(file);
+
+        let output = Command::new("ngspice")
+            .arg("-b")
+            .arg(&self.netlist_path)
+            .output()
+            .map_err(|e| format!("Worker {}: failed to spawn ngspice: {}", self.id, e))?;
+
+        if !output.status.success() {
+            return Err(format!(
+                "Worker {}: ngspice exited with {}: {}",
+                self.id,
+                output.status,
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|l| l.to_string())
+            .collect())
+    }
+}
+
+impl Drop for NgSpiceWorker {
+    fn drop(&mut self) {
+        if self.netlist_path.exists() {
+            let _ = std::fs::remove_file(&self.netlist_path);
+        }
+    }
+}
+
+/// A fixed pool of subprocess-backed ngspice workers used to fan a batch of
+/// candidate netlists across threads and join the results back in order.
+pub struct WorkerPool {
+    workers: Vec<NgSpiceWorker>,
+}
+
+impl WorkerPool {
+    /// Spawn a pool of `size` workers (each worker is just a scratch-file
+    /// handle; the actual `ngspice` process is launched per job in
+    /// [`WorkerPool::run_batch`]).
+    pub fn new(size: usize) -> Self {
+        let size = size.max(1);
+        Self {
+            workers: (0..size).map(NgSpiceWorker::new).collect(),
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Run each of `netlists` against the pool, splitting the slice of jobs
+    /// round-robin across worker threads, and return each job's captured
+    /// output in the same order as the input. A single job's failure does
+    /// not abort the others.
+    pub fn run_batch(&self, netlists: &[Vec<String>]) -> Vec<Result<Vec<String>, String>> {
+        let mut results: Vec<Option<Result<Vec<String>, String>>> =
+            (0..netlists.len()).map(|_| None).collect();
+
+        std::thread::scope(|scope| {
+            let mut handles = Vec::with_capacity(self.workers.len());
+
+            for (worker_idx, worker) in self.workers.iter().enumerate() {
+                let job_indices: Vec<usize> = (worker_idx..netlists.len())
+                    .step_by(self.workers.len())
+                    .collect();
+                let netlists = &netlists;
+
+                handles.push(scope.spawn(move || {
+                    job_indices
+                        .into_iter()
+                        .map(|i| (i, worker.run(&netlists[i])))
+                        .collect::<Vec<_>>()
+                }));
+            }
+
+            for handle in handles {
+                for (i, result) in handle.join().expect("worker thread panicked") {
+                    results[i] = Some(result);
+                }
+            }
+        });
+
+        results
+            .into_iter()
+            .map(|r| r.expect("every job index is assigned exactly one worker"))
+            .collect()
+    }
+}
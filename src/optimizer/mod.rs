@@ -6,12 +6,12 @@ mod xschem;
 pub use problem::CircuitProblem;
 pub use solver::{select_solver, CMAESOptimizer, NewtonOptimizer, ParticleOptimizer};
 pub use solver::{Problem, Solver, SolverResult};
-pub use xschem::XSchemNetlist;
+pub use xschem::{NetlistConfig, ParsedNetlist, XSchemNetlist};
 
-use crate::ngspice::{vecinfoall, vecvaluesall, NgSpice};
+use crate::error::OptError;
+use crate::ngspice::{vecinfoall, NgSpice};
 use crate::optimizer::problem::CircuitOptimizationCallback;
 use crate::types::*;
-use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use std::path::Path;
 use utils::NGSPICE_OUTPUT;
@@ -30,12 +30,16 @@ pub struct Optimizer {
     pub precision: f64,
     #[pyo3(get, set)]
     pub verbose: bool,
+    /// Force netlist regeneration even if a cached `.spice` output is newer
+    /// than the schematic/testbench it was generated from.
+    #[pyo3(get, set)]
+    pub force_netlist: bool,
 }
 
 #[pymethods]
 impl Optimizer {
     #[new]
-    #[pyo3(signature = (circuit="".to_string(), template=".".to_string(), solver="auto".to_string(), max_iterations=1000, precision=1e-6, verbose=false))]
+    #[pyo3(signature = (circuit="".to_string(), template=".".to_string(), solver="auto".to_string(), max_iterations=1000, precision=1e-6, verbose=false, force_netlist=false))]
     fn new(
         circuit: String,
         template: String,
@@ -43,6 +47,7 @@ impl Optimizer {
         max_iterations: u32,
         precision: f64,
         verbose: bool,
+        force_netlist: bool,
     ) -> Self {
         Self {
             circuit,
@@ -51,15 +56,18 @@ impl Optimizer {
             max_iterations,
             precision,
             verbose,
+            force_netlist,
         }
     }
 
+    #[pyo3(signature = (parameters, tests, targets, constraints, initial_solution=None))]
     fn optimize(
         &self,
         parameters: Vec<Py<Parameter>>,
         tests: Vec<Py<Test>>,
         targets: Vec<Py<Target>>,
         constraints: Vec<Py<ParameterConstraint>>,
+        initial_solution: Option<Py<OptimizationResult>>,
         py: Python,
     ) -> PyResult<Py<OptimizationResult>> {
         if self.verbose {
@@ -69,24 +77,40 @@ impl Optimizer {
         }
 
         // Extract native types from Python
-        let params_native: Vec<Parameter> =
+        let mut params_native: Vec<Parameter> =
             parameters.iter().map(|p| p.borrow(py).clone()).collect();
+
+        // Warm-start: seed each parameter's starting value from a previously
+        // saved result instead of restarting from `parameters`' defaults.
+        // Every solver reads its initial point from `CircuitProblem::initial_params`
+        // (CMA-ES's mean, PSO's first particle, Newton's starting iterate),
+        // so overwriting `value` here is enough to warm-start any of them.
+        if let Some(initial_solution) = &initial_solution {
+            let prior = initial_solution.borrow(py);
+            for param in &mut params_native {
+                if let Some(saved) = prior.get_parameter(&param.name) {
+                    param.value = saved.value.clamp(param.min_val, param.max_val);
+                }
+            }
+        }
+
         let tests_native: Vec<Test> = tests.iter().map(|t| t.borrow(py).clone()).collect();
         let targets_native: Vec<Target> = targets.iter().map(|t| t.borrow(py).clone()).collect();
         let mut constraints_native: Vec<ParameterConstraint> =
             constraints.iter().map(|c| c.borrow(py).clone()).collect();
 
         // Validate constraints
-        crate::validate_constraints(&mut constraints_native, &params_native)
-            .map_err(|e| PyValueError::new_err(format!("Validation failed: {}", e)))?;
+        crate::validate_constraints(&mut constraints_native, &params_native)?;
 
         let has_constraints = !constraints_native.is_empty();
 
         // Generate netlist
         let netlist_path_str = self.generate_netlist()?;
         let netlist_path = Path::new(&netlist_path_str);
-        let netlist_lines = XSchemNetlist::load_netlist(netlist_path)
-            .map_err(|e| PyValueError::new_err(format!("Failed to load netlist: {}", e)))?;
+        let netlist_lines = XSchemNetlist::load_netlist(netlist_path).map_err(|e| OptError::Netlist {
+            path: netlist_path_str.clone(),
+            reason: e,
+        })?;
 
         // Initialize NgSpice with callbacks
         let mut ngspice = NgSpice::new();
@@ -117,14 +141,6 @@ impl Optimizer {
         ) -> i32 {
             0
         }
-        extern "C" fn data_cb(
-            _data: *mut vecvaluesall,
-            _num: i32,
-            _id: i32,
-            _user: *mut std::ffi::c_void,
-        ) -> i32 {
-            0
-        }
         extern "C" fn init_data_cb(
             _data: *mut vecinfoall,
             _id: i32,
@@ -132,7 +148,8 @@ impl Optimizer {
         ) -> i32 {
             0
         }
-        extern "C" fn bg_thread_cb(_running: bool, _id: i32, _data: *mut std::ffi::c_void) -> i32 {
+        extern "C" fn bg_thread_cb(running: bool, _id: i32, _data: *mut std::ffi::c_void) -> i32 {
+            crate::ngspice::notify_bg_state_changed(running);
             0
         }
 
@@ -141,11 +158,14 @@ impl Optimizer {
                 Some(print_cb),
                 Some(stat_cb),
                 Some(exit_cb),
-                Some(data_cb),
+                Some(crate::ngspice::data_sink_callback),
                 Some(init_data_cb),
                 Some(bg_thread_cb),
             )
-            .map_err(|e| PyValueError::new_err(format!("NgSpice init failed: {}", e)))?;
+            .map_err(|e| OptError::Simulation {
+                command: "ngSpice_Init".to_string(),
+                reason: e,
+            })?;
 
         if self.verbose {
             println!("✓ NgSpice initialized");
@@ -161,14 +181,17 @@ impl Optimizer {
             netlist_lines,
             self.verbose,
         )
-        .map_err(|e| PyValueError::new_err(e))?;
+        .map_err(|e| OptError::Simulation {
+            command: "source".to_string(),
+            reason: e,
+        })?;
 
         // Create callback for tracking/display
         let param_names: Vec<String> = params_native.iter().map(|p| p.name.clone()).collect();
         let mut callback = CircuitOptimizationCallback::new(
             self.verbose,
             self.max_iterations,
-            targets_native,
+            targets_native.clone(),
             param_names,
             &problem,
         );
@@ -203,7 +226,7 @@ impl Optimizer {
         // Run optimization - NOW WITH CALLBACK!
         let result = solver
             .solve(&problem, &mut callback)
-            .map_err(|e| PyValueError::new_err(e))?;
+            .map_err(|e| OptError::Solver { reason: e })?;
 
         if self.verbose {
             println!("\n=== OPTIMIZATION COMPLETE ===");
@@ -223,9 +246,31 @@ impl Optimizer {
                 value,
                 min_val: def.min_val,
                 max_val: def.max_val,
+                integer: def.integer,
             })
             .collect();
 
+        // Per-target breakdown of the final cost, so a caller can see which
+        // target dominates it instead of only the summed scalar.
+        let target_breakdown = (|| -> Result<Vec<TargetContribution>, String> {
+            problem.update_parameters(&result.params)?;
+            problem.execute_measurements()?;
+            let metrics = problem.extract_metrics()?;
+            Ok(targets_native
+                .iter()
+                .map(|target| {
+                    let measured = *metrics.get(&target.metric).unwrap_or(&0.0);
+                    TargetContribution {
+                        metric: target.metric.clone(),
+                        measured,
+                        target: target.value,
+                        weighted_residual: target.compute_cost(measured),
+                    }
+                })
+                .collect())
+        })()
+        .unwrap_or_default();
+
         Py::new(
             py,
             OptimizationResult {
@@ -234,6 +279,7 @@ impl Optimizer {
                 iterations: result.iterations,
                 message: result.message,
                 parameters: final_params,
+                target_breakdown,
             },
         )
     }
@@ -251,24 +297,37 @@ impl Optimizer {
         // Check if schematic file
         if circuit_path.extension().and_then(|s| s.to_str()) == Some("sch") {
             // Verify testbench file
-            let filename = circuit_path
-                .file_name()
-                .and_then(|n| n.to_str())
-                .ok_or_else(|| PyValueError::new_err("Invalid circuit filename"))?;
+            let filename = circuit_path.file_name().and_then(|n| n.to_str()).ok_or_else(|| {
+                OptError::Netlist {
+                    path: circuit_path.to_string_lossy().to_string(),
+                    reason: "invalid circuit filename".to_string(),
+                }
+            })?;
 
             if !filename.ends_with("_tb.sch") {
-                return Err(PyValueError::new_err(format!(
-                    "Circuit must be a testbench file (ending in _tb.sch), got: {}",
-                    filename
-                )));
+                return Err(OptError::Netlist {
+                    path: filename.to_string(),
+                    reason: "circuit must be a testbench file (ending in _tb.sch)".to_string(),
+                }
+                .into());
             }
 
-            let xschem = XSchemNetlist::new(&circuit_path)
-                .map_err(|e| PyValueError::new_err(format!("XSchem error: {}", e)))?;
+            let xschem = XSchemNetlist::new(&circuit_path).map_err(|e| OptError::Netlist {
+                path: circuit_path.to_string_lossy().to_string(),
+                reason: e,
+            })?;
 
             let netlist_path = xschem
-                .generate_netlist(Path::new(&self.template), self.verbose)
-                .map_err(|e| PyValueError::new_err(format!("Netlist generation failed: {}", e)))?;
+                .generate_netlist(
+                    Path::new(&self.template),
+                    &NetlistConfig::sky130(),
+                    self.force_netlist,
+                    self.verbose,
+                )
+                .map_err(|e| OptError::Netlist {
+                    path: circuit_path.to_string_lossy().to_string(),
+                    reason: e,
+                })?;
 
             if self.verbose {
                 println!("✓ Netlist generated: {}", netlist_path.display());
@@ -292,8 +351,7 @@ impl Optimizer {
         let mut constraints_native: Vec<ParameterConstraint> =
             constraints.iter().map(|c| c.borrow(py).clone()).collect();
 
-        crate::validate_constraints(&mut constraints_native, &params_native)
-            .map_err(|e| PyValueError::new_err(e))?;
+        crate::validate_constraints(&mut constraints_native, &params_native)?;
 
         for (py_constraint, native_constraint) in constraints.iter().zip(constraints_native.iter())
         {
@@ -246,12 +246,54 @@ impl CircuitProblem {
                 }
             }
 
+            // Parameterize passive/source devices (R, C, L, V, I), whose value is
+            // a positional token after the two node names rather than a keyed
+            // `ptype=` pair.
+            if Self::starts_with_passive_prefix(trimmed) {
+                let comp_name = trimmed.split_whitespace().next().unwrap_or("");
+                if let Some(params) = component_params.get(comp_name) {
+                    if let Some((_, pname)) = params.iter().find(|(ptype, _)| ptype == "value") {
+                        result.push(Self::parameterize_positional_value(line, pname));
+                        continue;
+                    }
+                }
+            }
+
             result.push(line.clone());
         }
 
         Ok(result)
     }
 
+    /// Device prefixes whose value is the third positional field (after the
+    /// component name and two node names): resistors, capacitors, inductors,
+    /// voltage sources, current sources.
+    fn starts_with_passive_prefix(trimmed: &str) -> bool {
+        trimmed
+            .chars()
+            .next()
+            .map(|c| matches!(c, 'R' | 'C' | 'L' | 'V' | 'I'))
+            .unwrap_or(false)
+    }
+
+    /// Replace a passive/source device's positional value field (the token
+    /// following the two node names) with a `{parameter_name}` reference.
+    fn parameterize_positional_value(line: &str, pname: &str) -> String {
+        let mut fields = line.split_whitespace();
+        let (Some(name), Some(pos), Some(neg), Some(_value)) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            return line.to_string();
+        };
+        let rest: Vec<&str> = fields.collect();
+        let mut replaced = format!("{} {} {} {{{}}}", name, pos, neg, pname);
+        for field in rest {
+            replaced.push(' ');
+            replaced.push_str(field);
+        }
+        replaced
+    }
+
     /// Build mapping from component names to their parameters
     fn build_component_param_map(
         parameters: &[Parameter],
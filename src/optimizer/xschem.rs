@@ -11,6 +11,56 @@ pub enum FileType {
     Invalid,
 }
 
+/// PDK-specific paths `generate_netlist` needs to build an `xschemrc`,
+/// previously hardcoded to the sky130/volare layout. Construct one per PDK
+/// (see [`NetlistConfig::sky130`] for the old default) instead of editing
+/// source to netlist against a different process.
+#[derive(Debug, Clone)]
+pub struct NetlistConfig {
+    /// Root `xschemrc` to `source` (the PDK's own xschem setup script).
+    pub pdk_root: PathBuf,
+    /// Device models directory (`SKYWATER_MODELS`-equivalent).
+    pub models_path: PathBuf,
+    /// Standard-cell SPICE directory (`SKYWATER_STDCELLS`-equivalent).
+    pub stdcell_path: PathBuf,
+    /// Extra library search path appended to `XSCHEM_LIBRARY_PATH`.
+    pub library_path: PathBuf,
+    /// Value for xschem's `netlist_type` setting (e.g. `"spice"`).
+    pub netlist_type: String,
+}
+
+impl NetlistConfig {
+    pub fn new(
+        pdk_root: impl Into<PathBuf>,
+        models_path: impl Into<PathBuf>,
+        stdcell_path: impl Into<PathBuf>,
+        library_path: impl Into<PathBuf>,
+        netlist_type: impl Into<String>,
+    ) -> Self {
+        Self {
+            pdk_root: pdk_root.into(),
+            models_path: models_path.into(),
+            stdcell_path: stdcell_path.into(),
+            library_path: library_path.into(),
+            netlist_type: netlist_type.into(),
+        }
+    }
+
+    /// The project's original hardcoded sky130/volare configuration,
+    /// preserved as the default so existing callers keep working unchanged.
+    /// `$env(HOME)` is left as a literal Tcl expression, evaluated by
+    /// xschem itself when it sources the generated `xschemrc`.
+    pub fn sky130() -> Self {
+        Self::new(
+            "$env(HOME)/.volare/volare/sky130/versions/0fe599b2afb6708d281543108caf8310912f54af/sky130A/libs.tech/xschem/xschemrc",
+            "$env(HOME)/.volare/sky130A/libs.tech/ngspice",
+            "$env(HOME)/.volare/sky130A/libs.ref/sky130_fd_sc_hd/spice",
+            "",
+            "spice",
+        )
+    }
+}
+
 pub struct XSchemNetlist {
     file_path: PathBuf,
 }
@@ -66,13 +116,20 @@ impl XSchemNetlist {
 
     /// Generate netlist from the schematic file (prefers testbench if available)
     /// Returns absolute path to the generated netlist
-    pub fn generate_netlist(&self, template_dir: &Path, verbose: bool) -> Result<PathBuf, String> {
+    pub fn generate_netlist(
+        &self,
+        template_dir: &Path,
+        config: &NetlistConfig,
+        force: bool,
+        verbose: bool,
+    ) -> Result<PathBuf, String> {
         // Use testbench if available, otherwise use schematic
-        let (file_to_netlist, is_testbench) = if let Some(tb_path) = self.find_testbench() {
+        let testbench = self.find_testbench();
+        let (file_to_netlist, is_testbench) = if let Some(tb_path) = &testbench {
             if verbose {
                 println!("Found testbench: {}", tb_path.display());
             }
-            (tb_path, true)
+            (tb_path.clone(), true)
         } else {
             (self.file_path.clone(), false)
         };
@@ -90,6 +147,31 @@ impl XSchemNetlist {
             .ok_or_else(|| "Invalid filename".to_string())?;
         let netlist_path = schematic_dir.join(format!("{}.spice", netlist_name));
 
+        // Skip re-running xschem if the cached netlist is newer than every
+        // schematic input it was generated from - re-netlisting the same
+        // topology hundreds of times in a sizing loop is the dominant
+        // per-iteration cost otherwise.
+        if !force {
+            let mut inputs = vec![self.file_path.clone()];
+            if let Some(tb_path) = &testbench {
+                inputs.push(tb_path.clone());
+            }
+            if is_netlist_fresh(&netlist_path, &inputs) {
+                if verbose {
+                    println!(
+                        "Using cached netlist (up to date): {}",
+                        netlist_path.display()
+                    );
+                }
+                return fs::canonicalize(&netlist_path)
+                    .map_err(|e| format!("Failed to get absolute netlist path: {}", e));
+            }
+        }
+
+        if verbose {
+            println!("Cache miss - regenerating netlist: {}", netlist_path.display());
+        }
+
         // Get current working directory
         let cwd = std::env::current_dir()
             .map_err(|e| format!("Failed to get current directory: {}", e))?;
@@ -103,9 +185,9 @@ impl XSchemNetlist {
 
         // Create xschemrc content
         let xschemrc_content = format!(
-            r#"source $env(HOME)/.volare/volare/sky130/versions/0fe599b2afb6708d281543108caf8310912f54af/sky130A/libs.tech/xschem/xschemrc
-set SKYWATER_MODELS "$env(HOME)/.volare/sky130A/libs.tech/ngspice"
-set SKYWATER_STDCELLS "$env(HOME)/.volare/sky130A/libs.ref/sky130_fd_sc_hd/spice"
+            r#"source {}
+set SKYWATER_MODELS "{}"
+set SKYWATER_STDCELLS "{}"
 puts "PDK set SKYWATER_MODELS to: $SKYWATER_MODELS"
 puts "PDK set SKYWATER_STDCELLS to: $SKYWATER_STDCELLS"
 #### PROJECT CONFIGURATION
@@ -118,13 +200,19 @@ set editor "vim"
 set netlist_dir {}
 file mkdir $netlist_dir
 set XSCHEM_NETLIST_DIR $netlist_dir
-set netlist_type spice
+set netlist_type {}
 set spice_netlist 1
 
+append XSCHEM_LIBRARY_PATH :{}
 append XSCHEM_LIBRARY_PATH :{}
 "#,
+            config.pdk_root.display(),
+            config.models_path.display(),
+            config.stdcell_path.display(),
+            abs_template_dir.display(),
+            config.netlist_type,
             abs_template_dir.display(),
-            abs_template_dir.display()
+            config.library_path.display(),
         );
 
         // Write xschemrc file
@@ -201,4 +289,140 @@ append XSCHEM_LIBRARY_PATH :{}
             .map(String::from)
             .collect())
     }
+
+    /// Parse netlist lines (as returned by [`Self::load_netlist`]) into
+    /// structured subcircuits, instances, and `.param` values, instead of
+    /// leaving callers to string-scrape the raw SPICE.
+    pub fn parse_netlist(lines: &[String]) -> ParsedNetlist {
+        ParsedNetlist::from_lines(lines)
+    }
+}
+
+/// Device kind inferred from an instance line's leading reference
+/// designator, per SPICE convention.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstanceKind {
+    /// `X...` - subcircuit call.
+    Subckt,
+    /// `M...` - MOSFET.
+    Mosfet,
+    /// `R...` - resistor.
+    Resistor,
+    /// `C...` - capacitor.
+    Capacitor,
+}
+
+/// A `.subckt ... .ends` definition's name and port list.
+#[derive(Debug, Clone)]
+pub struct Subckt {
+    pub name: String,
+    pub ports: Vec<String>,
+}
+
+/// One instance (`X`/`M`/`R`/`C`) line: its reference designator and the
+/// remaining whitespace-separated tokens (nodes, model/subckt name, and any
+/// `name=value` parameters), left unsplit since their shape varies by kind.
+#[derive(Debug, Clone)]
+pub struct Instance {
+    pub name: String,
+    pub kind: InstanceKind,
+    pub tokens: Vec<String>,
+}
+
+/// Structured view of a generated `.spice` netlist, produced by
+/// [`XSchemNetlist::parse_netlist`] so the optimizer can map schematic
+/// parameters to constraint variables programmatically instead of
+/// string-scraping the file.
+#[derive(Debug, Clone, Default)]
+pub struct ParsedNetlist {
+    pub subckts: Vec<Subckt>,
+    pub instances: Vec<Instance>,
+    pub params: Vec<(String, f64)>,
+}
+
+impl ParsedNetlist {
+    fn from_lines(lines: &[String]) -> Self {
+        // Stitch SPICE continuation lines (those beginning with `+`) onto
+        // the logical line they continue.
+        let mut stitched: Vec<String> = Vec::with_capacity(lines.len());
+        for line in lines {
+            if let Some(cont) = line.strip_prefix('+') {
+                if let Some(last) = stitched.last_mut() {
+                    *last = format!("{} {}", last, cont.trim());
+                }
+                continue;
+            }
+            stitched.push(line.clone());
+        }
+
+        let mut result = ParsedNetlist::default();
+        let mut current_subckt: Option<Subckt> = None;
+
+        for line in &stitched {
+            let Some(first_char) = line.chars().next() else {
+                continue;
+            };
+            let lower = line.to_ascii_lowercase();
+
+            if lower.starts_with(".subckt") {
+                let tokens: Vec<&str> = line.split_whitespace().collect();
+                if let Some(&name) = tokens.get(1) {
+                    current_subckt = Some(Subckt {
+                        name: name.to_string(),
+                        ports: tokens[2..].iter().map(|s| s.to_string()).collect(),
+                    });
+                }
+            } else if lower.starts_with(".ends") {
+                if let Some(subckt) = current_subckt.take() {
+                    result.subckts.push(subckt);
+                }
+            } else if lower.starts_with(".param") {
+                for token in line.split_whitespace().skip(1) {
+                    if let Some((name, value)) = token.split_once('=') {
+                        if let Ok(value) = value.parse::<f64>() {
+                            result.params.push((name.to_string(), value));
+                        }
+                    }
+                }
+            } else if let Some(kind) = instance_kind(first_char) {
+                let tokens: Vec<&str> = line.split_whitespace().collect();
+                if let Some(&name) = tokens.first() {
+                    result.instances.push(Instance {
+                        name: name.to_string(),
+                        kind,
+                        tokens: tokens[1..].iter().map(|s| s.to_string()).collect(),
+                    });
+                }
+            }
+        }
+
+        result
+    }
+}
+
+/// Whether `netlist_path` exists and is at least as new as every path in
+/// `inputs`, per `std::fs::metadata`'s modification time. Missing/unreadable
+/// metadata (on either side) is treated as "not fresh" so the caller falls
+/// back to regenerating rather than trusting a stale or inaccessible cache.
+fn is_netlist_fresh(netlist_path: &Path, inputs: &[PathBuf]) -> bool {
+    let Ok(netlist_modified) = fs::metadata(netlist_path).and_then(|m| m.modified()) else {
+        return false;
+    };
+
+    inputs.iter().all(|input| {
+        fs::metadata(input)
+            .and_then(|m| m.modified())
+            .map(|input_modified| input_modified <= netlist_modified)
+            .unwrap_or(false)
+    })
+}
+
+fn instance_kind(designator: char) -> Option<InstanceKind> {
+    match designator.to_ascii_uppercase() {
+        'X' => Some(InstanceKind::Subckt),
+        'M' => Some(InstanceKind::Mosfet),
+        'R' => Some(InstanceKind::Resistor),
+        'C' => Some(InstanceKind::Capacitor),
+        _ => None,
+    }
 }
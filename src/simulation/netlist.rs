@@ -22,7 +22,11 @@ pub fn parameterize_netlist(
     result.push("".to_string());
     result.push("* === Optimization Parameters (Auto-generated) ===".to_string());
     for param in parameters {
-        result.push(format!(".param {} = {}", param.name, param.value));
+        result.push(format!(
+            ".param {} = {}",
+            param.name,
+            crate::units::format_spice_value(param.value)
+        ));
     }
     result.push("* === End Parameters ===".to_string());
     result.push("".to_string());
@@ -58,6 +62,18 @@ pub fn parameterize_netlist(
             }
         }
 
+        // Parameterize passive/source devices (R, C, L, V, I), whose value is a
+        // positional token after the two node names rather than a `ptype=` key.
+        if starts_with_passive_prefix(trimmed) {
+            let comp_name = trimmed.split_whitespace().next().unwrap_or("");
+            if let Some(params) = component_params.get(comp_name) {
+                if let Some((_, pname)) = params.iter().find(|(ptype, _)| ptype == "value") {
+                    result.push(parameterize_positional_value(line, pname));
+                    continue;
+                }
+            }
+        }
+
         result.push(line.clone());
     }
 
@@ -84,6 +100,36 @@ fn build_component_param_map(
     component_params
 }
 
+/// Device prefixes whose value is the third positional field (after the
+/// component name and two node names) rather than a keyed `ptype=value` pair:
+/// resistors, capacitors, inductors, voltage sources, current sources.
+fn starts_with_passive_prefix(trimmed: &str) -> bool {
+    trimmed
+        .chars()
+        .next()
+        .map(|c| matches!(c, 'R' | 'C' | 'L' | 'V' | 'I'))
+        .unwrap_or(false)
+}
+
+/// Replace a passive/source device's positional value field (the token
+/// following the two node names) with a `{parameter_name}` reference.
+fn parameterize_positional_value(line: &str, pname: &str) -> String {
+    let mut fields = line.split_whitespace();
+    let (Some(name), Some(pos), Some(neg), Some(value)) =
+        (fields.next(), fields.next(), fields.next(), fields.next())
+    else {
+        return line.to_string();
+    };
+    let rest: Vec<&str> = fields.collect();
+    let mut replaced = format!("{} {} {} {{{}}}", name, pos, neg, pname);
+    let _ = value;
+    for field in rest {
+        replaced.push(' ');
+        replaced.push_str(field);
+    }
+    replaced
+}
+
 /// Parameterize a single component line by replacing values with {param} references
 fn parameterize_component_line(line: &str, params: &[(String, String)]) -> String {
     let mut modified = line.to_string();
@@ -1,6 +1,7 @@
 use crate::expression::CompiledExpression;
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
 
 // ===== ENUMS =====
 
@@ -23,6 +24,87 @@ impl TargetMode {
     }
 }
 
+/// Why an optimization run stopped. Returned to Python as a typed value
+/// instead of a free-form string so callers can branch on it directly.
+#[pyclass(eq, eq_int)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TerminationReason {
+    MaxIters,
+    TargetCostReached,
+    AbsToleranceReached,
+    RelToleranceReached,
+    Stagnation,
+    AllTargetsMet,
+    UserInterrupt,
+    TimeBudgetExceeded,
+}
+
+#[pymethods]
+impl TerminationReason {
+    fn __repr__(&self) -> &str {
+        match self {
+            Self::MaxIters => "TerminationReason.MaxIters",
+            Self::TargetCostReached => "TerminationReason.TargetCostReached",
+            Self::AbsToleranceReached => "TerminationReason.AbsToleranceReached",
+            Self::RelToleranceReached => "TerminationReason.RelToleranceReached",
+            Self::Stagnation => "TerminationReason.Stagnation",
+            Self::AllTargetsMet => "TerminationReason.AllTargetsMet",
+            Self::UserInterrupt => "TerminationReason.UserInterrupt",
+            Self::TimeBudgetExceeded => "TerminationReason.TimeBudgetExceeded",
+        }
+    }
+
+    fn __str__(&self) -> &str {
+        match self {
+            Self::MaxIters => "maximum iterations reached",
+            Self::TargetCostReached => "target cost reached",
+            Self::AbsToleranceReached => "absolute tolerance reached",
+            Self::RelToleranceReached => "relative tolerance reached",
+            Self::Stagnation => "cost stagnated",
+            Self::AllTargetsMet => "all targets met",
+            Self::UserInterrupt => "interrupted by user",
+            Self::TimeBudgetExceeded => "time budget exceeded",
+        }
+    }
+}
+
+/// Overall feasibility of a [`crate::optimization::Solution`]: whether
+/// every target is met and every parameter constraint's relationship holds
+/// between its computed value and its target parameter's current value,
+/// within a small tolerance. A typed two-variant enum
+/// instead of a bare `bool` so Python callers get the same `Feasible` /
+/// `Infeasible` vocabulary a solver's solution object would use.
+#[pyclass(eq, eq_int)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Feasibility {
+    Feasible,
+    Infeasible,
+}
+
+#[pymethods]
+impl Feasibility {
+    fn __repr__(&self) -> &str {
+        match self {
+            Self::Feasible => "Feasibility.Feasible",
+            Self::Infeasible => "Feasibility.Infeasible",
+        }
+    }
+
+    fn __bool__(&self) -> bool {
+        matches!(self, Self::Feasible)
+    }
+}
+
+impl From<bool> for Feasibility {
+    fn from(feasible: bool) -> Self {
+        if feasible {
+            Self::Feasible
+        } else {
+            Self::Infeasible
+        }
+    }
+}
+
 #[pyclass(eq, eq_int)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum RelationshipType {
@@ -66,7 +148,7 @@ impl Environment {
 }
 
 #[pyclass]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Parameter {
     #[pyo3(get, set)]
     pub name: String,
@@ -76,17 +158,31 @@ pub struct Parameter {
     pub min_val: f64,
     #[pyo3(get, set)]
     pub max_val: f64,
+    /// True for genuinely discrete degrees of freedom (transistor
+    /// multiplicity `m`, finger count `nf`, ...): the optimizer rounds
+    /// these to whole units and branch-and-bounds over them rather than
+    /// snapping to the continuous Sky130 layout grid.
+    #[pyo3(get, set)]
+    pub integer: bool,
 }
 
 #[pymethods]
 impl Parameter {
     #[new]
-    fn new(name: String, value: f64, min_val: f64, max_val: f64) -> Self {
+    #[pyo3(signature = (name, value, min_val, max_val, integer=false))]
+    fn new(
+        name: String,
+        value: crate::units::SpiceValue,
+        min_val: crate::units::SpiceValue,
+        max_val: crate::units::SpiceValue,
+        integer: bool,
+    ) -> Self {
         Self {
             name,
-            value,
-            min_val,
-            max_val,
+            value: value.0,
+            min_val: min_val.0,
+            max_val: max_val.0,
+            integer,
         }
     }
 
@@ -304,8 +400,38 @@ impl ParameterConstraint {
     }
 }
 
+/// One `Target`'s contribution to the final cost: its measured value against
+/// the target value and the weighted residual [`Target::compute_cost`] would
+/// score it as, so a caller can tell which target dominates `OptimizationResult.cost`
+/// instead of only seeing the summed scalar.
 #[pyclass]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TargetContribution {
+    #[pyo3(get)]
+    pub metric: String,
+    #[pyo3(get)]
+    pub measured: f64,
+    #[pyo3(get)]
+    pub target: f64,
+    #[pyo3(get)]
+    pub weighted_residual: f64,
+}
+
+#[pymethods]
+impl TargetContribution {
+    #[new]
+    fn new(metric: String, measured: f64, target: f64, weighted_residual: f64) -> Self {
+        Self {
+            metric,
+            measured,
+            target,
+            weighted_residual,
+        }
+    }
+}
+
+#[pyclass]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct OptimizationResult {
     #[pyo3(get, set)]
     pub success: bool,
@@ -317,17 +443,26 @@ pub struct OptimizationResult {
     pub message: String,
     #[pyo3(get)]
     pub parameters: Vec<Parameter>,
+    /// Per-target breakdown of `cost`, see [`TargetContribution`]. Empty
+    /// when none was computed: `Optimizer.optimize` leaves it empty if
+    /// re-measuring the final point fails, and results saved before this
+    /// field existed or built by a bare `OptimizationResult::new` carry none.
+    #[pyo3(get)]
+    #[serde(default)]
+    pub target_breakdown: Vec<TargetContribution>,
 }
 
 #[pymethods]
 impl OptimizationResult {
     #[new]
+    #[pyo3(signature = (success, parameters, cost, iterations, message, target_breakdown=Vec::new()))]
     fn new(
         success: bool,
         parameters: Vec<Parameter>,
         cost: f64,
         iterations: u32,
         message: String,
+        target_breakdown: Vec<TargetContribution>,
     ) -> Self {
         Self {
             success,
@@ -335,10 +470,26 @@ impl OptimizationResult {
             iterations,
             message,
             parameters,
+            target_breakdown,
         }
     }
 
     pub fn get_parameter(&self, name: &str) -> Option<Parameter> {
         self.parameters.iter().find(|p| p.name == name).cloned()
     }
+
+    /// Serialize to a JSON string, so a result can be saved and later fed
+    /// back in as `Optimizer.optimize`'s `initial_solution` to warm-start a
+    /// follow-up run.
+    pub fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| PyValueError::new_err(format!("Failed to serialize result: {}", e)))
+    }
+
+    /// Deserialize a result previously written by `to_json`.
+    #[staticmethod]
+    pub fn from_json(json: &str) -> PyResult<Self> {
+        serde_json::from_str(json)
+            .map_err(|e| PyValueError::new_err(format!("Failed to parse result: {}", e)))
+    }
 }
@@ -0,0 +1,143 @@
+//! SPICE engineering-suffix numeric parsing and formatting - analog
+//! parameter bounds are more natural to read and write as `"4.7u"` or
+//! `"2.2meg"` than as bare `f64`s like `4.7e-6`/`2.2e6`. [`SpiceValue`] is a
+//! `FromStr`/`Display` pair around the conversion, and also implements
+//! `FromPyObject` so pyo3 constructors (see [`crate::types::Parameter`])
+//! can accept either a plain float or a suffixed string from Python.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::fmt;
+use std::str::FromStr;
+
+/// Suffix -> multiplier table, checked in this order so `"meg"` (mega,
+/// 1e6) is matched before the single trailing `"g"` or `"m"` it would
+/// otherwise be mistaken for - SPICE's classic mega/milli ambiguity, where
+/// `"m"` alone means milli and only the three-letter `"meg"` means mega.
+const SUFFIXES: &[(&str, f64)] = &[
+    ("meg", 1e6),
+    ("t", 1e12),
+    ("g", 1e9),
+    ("k", 1e3),
+    ("m", 1e-3),
+    ("u", 1e-6),
+    ("n", 1e-9),
+    ("p", 1e-12),
+    ("f", 1e-15),
+];
+
+/// A numeric value parsed from (or formatted to) a SPICE engineering
+/// suffix string, e.g. `"4.7u"` <-> `4.7e-6`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpiceValue(pub f64);
+
+impl SpiceValue {
+    /// The underlying value with all suffix scaling already applied.
+    pub fn to_f64(self) -> f64 {
+        self.0
+    }
+}
+
+impl FromStr for SpiceValue {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        let lower = trimmed.to_lowercase();
+
+        // Split into the leading numeric mantissa and its trailing
+        // alphabetic tail, e.g. "4.7meg" -> ("4.7", "meg"), "10uF" ->
+        // ("10", "uf"). The tail may be just a scale suffix, a scale
+        // suffix followed by an ignored unit letter (the "F" in "10uF"),
+        // or, with no recognized suffix, a bare ignored unit.
+        let split_idx = lower
+            .find(|c: char| c.is_ascii_alphabetic())
+            .unwrap_or(lower.len());
+        let (mantissa, tail) = lower.split_at(split_idx);
+
+        if mantissa.is_empty() {
+            return Err(format!("invalid SPICE value {:?}: missing mantissa", s));
+        }
+        let value: f64 = mantissa
+            .parse()
+            .map_err(|e| format!("invalid SPICE value {:?}: {}", s, e))?;
+
+        if tail.is_empty() {
+            return Ok(SpiceValue(value));
+        }
+
+        for &(suffix, multiplier) in SUFFIXES {
+            if tail.starts_with(suffix) {
+                return Ok(SpiceValue(value * multiplier));
+            }
+        }
+
+        // Unrecognized tail (e.g. a bare unit like "hz") - no scaling applied.
+        Ok(SpiceValue(value))
+    }
+}
+
+impl fmt::Display for SpiceValue {
+    /// The most compact suffixed form that `FromStr` parses back - the
+    /// largest suffix whose magnitude doesn't put the mantissa below 1, with
+    /// the mantissa rounded to six decimal places and trimmed of trailing
+    /// zeros so `.param` lines stay readable. Digits past the sixth decimal
+    /// of the mantissa are lost, so parsing back is exact only to that
+    /// precision.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = self.0;
+        if value == 0.0 {
+            return write!(f, "0");
+        }
+
+        let magnitude = value.abs();
+        for &(suffix, multiplier) in &[
+            ("t", 1e12),
+            ("g", 1e9),
+            ("meg", 1e6),
+            ("k", 1e3),
+            ("", 1.0),
+            ("m", 1e-3),
+            ("u", 1e-6),
+            ("n", 1e-9),
+            ("p", 1e-12),
+            ("f", 1e-15),
+        ] {
+            if magnitude >= multiplier {
+                let mantissa = value / multiplier;
+                let rounded = format!("{:.6}", mantissa);
+                let trimmed = rounded.trim_end_matches('0').trim_end_matches('.');
+                return write!(f, "{}{}", trimmed, suffix);
+            }
+        }
+
+        // Smaller than femto - nothing left to scale by.
+        write!(f, "{}f", value * 1e15)
+    }
+}
+
+impl<'py> FromPyObject<'py> for SpiceValue {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if let Ok(value) = ob.extract::<f64>() {
+            return Ok(SpiceValue(value));
+        }
+        let s: String = ob.extract()?;
+        s.parse().map_err(PyValueError::new_err)
+    }
+}
+
+/// Parse a SPICE engineering-suffixed string into its `f64` value, exposed
+/// to Python so users can write parameter bounds in natural units.
+#[pyfunction]
+pub fn parse_spice_value(s: &str) -> PyResult<f64> {
+    s.parse::<SpiceValue>()
+        .map(|v| v.0)
+        .map_err(PyValueError::new_err)
+}
+
+/// Format an `f64` as the most compact SPICE engineering-suffixed string,
+/// exposed to Python for the inverse direction.
+#[pyfunction]
+pub fn format_spice_value(value: f64) -> String {
+    SpiceValue(value).to_string()
+}